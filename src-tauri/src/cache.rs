@@ -0,0 +1,519 @@
+use crate::error::{AppError, ErrorCode};
+use crate::search::SearchOutcome;
+use crate::sources::owner_of;
+use crate::{PullRequest, Repository, ReviewedPullRequest};
+use chrono::{Duration, NaiveDate, Utc};
+use rusqlite::{Connection, OptionalExtension, params};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use std::path::Path;
+use std::sync::Mutex;
+
+const SCHEMA: &str = "
+    CREATE TABLE IF NOT EXISTS activity (
+        scope TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        url TEXT NOT NULL,
+        day TEXT NOT NULL,
+        owner TEXT NOT NULL,
+        data TEXT NOT NULL,
+        PRIMARY KEY (scope, kind, url)
+    );
+    CREATE INDEX IF NOT EXISTS activity_day ON activity (scope, kind, day);
+    CREATE TABLE IF NOT EXISTS coverage (
+        scope TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        start_day TEXT NOT NULL,
+        end_day TEXT NOT NULL,
+        PRIMARY KEY (scope, kind)
+    );
+    CREATE TABLE IF NOT EXISTS last_sync (
+        scope TEXT NOT NULL,
+        kind TEXT NOT NULL,
+        synced_at TEXT NOT NULL,
+        PRIMARY KEY (scope, kind)
+    );
+    CREATE TABLE IF NOT EXISTS accounts (
+        host TEXT PRIMARY KEY,
+        login TEXT NOT NULL
+    );
+";
+
+/// An activity record the cache can store: something with a URL, a merge day and a repository.
+pub trait Cacheable: Serialize + DeserializeOwned {
+    const KIND: &'static str;
+
+    fn url(&self) -> &str;
+    fn day(&self) -> Option<&str>;
+    fn repository(&self) -> &Repository;
+}
+
+impl Cacheable for PullRequest {
+    const KIND: &'static str = "authored";
+
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn day(&self) -> Option<&str> {
+        self.closed_at.get(..10)
+    }
+
+    fn repository(&self) -> &Repository {
+        &self.repository
+    }
+}
+
+impl Cacheable for ReviewedPullRequest {
+    const KIND: &'static str = "reviewed";
+
+    fn url(&self) -> &str {
+        &self.url
+    }
+
+    fn day(&self) -> Option<&str> {
+        self.closed_at
+            .as_deref()
+            .and_then(|closed_at| closed_at.get(..10))
+    }
+
+    fn repository(&self) -> &Repository {
+        &self.repository
+    }
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+fn format_date(date: NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+/// Whether `start..=end` overlaps or directly touches the cached range.
+fn touches(covered: (NaiveDate, NaiveDate), start: NaiveDate, end: NaiveDate) -> bool {
+    let (covered_start, covered_end) = covered;
+    end + Duration::days(1) >= covered_start && start <= covered_end + Duration::days(1)
+}
+
+/// The parts of `start..=end` that still have to be fetched, given the range already cached.
+///
+/// When the request overlaps or touches the cached range, only the missing edges are
+/// fetched so the cached range stays contiguous. A request that is disjoint from it is
+/// fetched in full.
+fn gaps(
+    covered: Option<(NaiveDate, NaiveDate)>,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Vec<(NaiveDate, NaiveDate)> {
+    let Some((covered_start, covered_end)) = covered.filter(|c| touches(*c, start, end)) else {
+        return vec![(start, end)];
+    };
+
+    let mut gaps = Vec::new();
+    if start < covered_start {
+        gaps.push((start, covered_start - Duration::days(1)));
+    }
+    if end > covered_end {
+        gaps.push((covered_end + Duration::days(1), end));
+    }
+    gaps
+}
+
+/// The cached range after `start..=end` has been fetched.
+fn merge_coverage(
+    covered: Option<(NaiveDate, NaiveDate)>,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> (NaiveDate, NaiveDate) {
+    match covered.filter(|c| touches(*c, start, end)) {
+        Some((covered_start, covered_end)) => (covered_start.min(start), covered_end.max(end)),
+        None => (start, end),
+    }
+}
+
+/// Local SQLite store of fetched activity, keyed by scope (usually the `login@host`
+/// account it came from).
+///
+/// For each scope and kind of record it remembers the contiguous range of days it has
+/// fully synced, so repeated queries only fetch the days outside that range. Today is
+/// never marked as synced, since more pull requests can still be merged.
+pub struct ActivityCache {
+    conn: Mutex<Connection>,
+}
+
+impl ActivityCache {
+    pub fn open(path: &Path) -> Result<Self, AppError> {
+        let conn = Connection::open(path).map_err(|e| {
+            AppError::new(
+                ErrorCode::Cache,
+                format!("Failed to open activity cache: {}", e),
+            )
+        })?;
+        Self::with_connection(conn)
+    }
+
+    #[cfg(test)]
+    pub fn open_in_memory() -> Result<Self, AppError> {
+        let conn = Connection::open_in_memory().map_err(|e| {
+            AppError::new(
+                ErrorCode::Cache,
+                format!("Failed to open activity cache: {}", e),
+            )
+        })?;
+        Self::with_connection(conn)
+    }
+
+    fn with_connection(conn: Connection) -> Result<Self, AppError> {
+        conn.execute_batch(SCHEMA).map_err(|e| {
+            AppError::new(
+                ErrorCode::Cache,
+                format!("Failed to initialize activity cache: {}", e),
+            )
+        })?;
+        Ok(Self {
+            conn: Mutex::new(conn),
+        })
+    }
+
+    fn conn(&self) -> std::sync::MutexGuard<'_, Connection> {
+        self.conn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn coverage(
+        &self,
+        scope: &str,
+        kind: &str,
+    ) -> Result<Option<(NaiveDate, NaiveDate)>, AppError> {
+        let row: Option<(String, String)> = self
+            .conn()
+            .query_row(
+                "SELECT start_day, end_day FROM coverage WHERE scope = ?1 AND kind = ?2",
+                params![scope, kind],
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to read activity cache: {}", e),
+                )
+            })?;
+
+        Ok(row.and_then(|(start, end)| Some((parse_date(&start)?, parse_date(&end)?))))
+    }
+
+    fn set_coverage(
+        &self,
+        scope: &str,
+        kind: &str,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> Result<(), AppError> {
+        self.conn()
+            .execute(
+                "INSERT OR REPLACE INTO coverage (scope, kind, start_day, end_day) VALUES (?1, ?2, ?3, ?4)",
+                params![scope, kind, format_date(start), format_date(end)],
+            )
+            .map_err(|e| AppError::new(ErrorCode::Cache, format!("Failed to write activity cache: {}", e)))?;
+        Ok(())
+    }
+
+    fn store<T: Cacheable>(&self, scope: &str, items: &[T]) -> Result<(), AppError> {
+        let mut conn = self.conn();
+        let tx = conn.transaction().map_err(|e| {
+            AppError::new(
+                ErrorCode::Cache,
+                format!("Failed to write activity cache: {}", e),
+            )
+        })?;
+
+        for item in items {
+            let Some(day) = item.day() else {
+                continue;
+            };
+            let owner = owner_of(&item.repository().name_with_owner).to_lowercase();
+            let data = serde_json::to_string(item).map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to serialize cached activity: {}", e),
+                )
+            })?;
+
+            tx.execute(
+                "INSERT OR REPLACE INTO activity (scope, kind, url, day, owner, data) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+                params![scope, T::KIND, item.url(), day, owner, data],
+            )
+            .map_err(|e| AppError::new(ErrorCode::Cache, format!("Failed to write activity cache: {}", e)))?;
+        }
+
+        tx.commit().map_err(|e| {
+            AppError::new(
+                ErrorCode::Cache,
+                format!("Failed to write activity cache: {}", e),
+            )
+        })
+    }
+
+    /// Cached records merged between `start` and `end` (inclusive), optionally limited to one owner.
+    pub fn load<T: Cacheable>(
+        &self,
+        scope: &str,
+        start: &str,
+        end: &str,
+        owner: Option<&str>,
+    ) -> Result<Vec<T>, AppError> {
+        let conn = self.conn();
+        let mut statement = conn
+            .prepare(
+                "SELECT data FROM activity
+                 WHERE scope = ?1 AND kind = ?2 AND day >= ?3 AND day <= ?4
+                   AND (?5 IS NULL OR owner = ?5)
+                 ORDER BY day DESC, url",
+            )
+            .map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to read activity cache: {}", e),
+                )
+            })?;
+
+        let owner = owner.map(|o| o.to_lowercase());
+        let rows = statement
+            .query_map(params![scope, T::KIND, start, end, owner], |row| {
+                row.get::<_, String>(0)
+            })
+            .map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to read activity cache: {}", e),
+                )
+            })?;
+
+        rows.map(|row| {
+            let data = row.map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to read activity cache: {}", e),
+                )
+            })?;
+            serde_json::from_str(&data).map_err(|e| {
+                AppError::new(
+                    ErrorCode::Parse,
+                    format!("Failed to parse cached activity: {}", e),
+                )
+            })
+        })
+        .collect()
+    }
+
+    /// Fetches whatever part of `start..end` isn't cached yet for `scope`, stores it,
+    /// and returns everything cached for the range.
+    ///
+    /// `fetch` receives the start and end day of each missing range and searches all
+    /// of it. The outcome is incomplete when any of those searches was.
+    pub fn sync<T, F>(
+        &self,
+        scope: &str,
+        start: &str,
+        end: &str,
+        today: NaiveDate,
+        owner: Option<&str>,
+        mut fetch: F,
+    ) -> Result<SearchOutcome<T>, AppError>
+    where
+        T: Cacheable,
+        F: FnMut(&str, &str) -> Result<SearchOutcome<T>, AppError>,
+    {
+        let (Some(start_day), Some(end_day)) = (parse_date(start), parse_date(end)) else {
+            return fetch(start, end);
+        };
+
+        let covered = self.coverage(scope, T::KIND)?;
+        let missing = gaps(covered, start_day, end_day);
+        let mut complete = true;
+
+        for (gap_start, gap_end) in &missing {
+            let outcome = fetch(&format_date(*gap_start), &format_date(*gap_end))?;
+            complete &= outcome.complete;
+            self.store(scope, &outcome.items)?;
+        }
+
+        if !missing.is_empty() {
+            self.set_last_synced_at(scope, T::KIND, &Utc::now().to_rfc3339())?;
+        }
+
+        // Only mark days as synced once every result for them has been stored.
+        if !missing.is_empty() && complete {
+            let (new_start, new_end) = merge_coverage(covered, start_day, end_day);
+            let new_end = new_end.min(today - Duration::days(1));
+
+            if new_start <= new_end {
+                self.set_coverage(scope, T::KIND, new_start, new_end)?;
+            }
+        }
+
+        Ok(SearchOutcome {
+            items: self.load(scope, start, end, owner)?,
+            complete,
+        })
+    }
+
+    /// Whether every day between `start` and `end` (inclusive) has been synced for `kind`.
+    pub fn covers(
+        &self,
+        scope: &str,
+        kind: &str,
+        start: &str,
+        end: &str,
+    ) -> Result<bool, AppError> {
+        let (Some(start_day), Some(end_day)) = (parse_date(start), parse_date(end)) else {
+            return Ok(false);
+        };
+        Ok(gaps(self.coverage(scope, kind)?, start_day, end_day).is_empty())
+    }
+
+    /// When records of this kind were last fetched successfully for `scope`, as RFC 3339.
+    pub fn last_synced_at(&self, scope: &str, kind: &str) -> Result<Option<String>, AppError> {
+        self.conn()
+            .query_row(
+                "SELECT synced_at FROM last_sync WHERE scope = ?1 AND kind = ?2",
+                params![scope, kind],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to read activity cache: {}", e),
+                )
+            })
+    }
+
+    fn set_last_synced_at(&self, scope: &str, kind: &str, synced_at: &str) -> Result<(), AppError> {
+        self.conn()
+            .execute(
+                "INSERT OR REPLACE INTO last_sync (scope, kind, synced_at) VALUES (?1, ?2, ?3)",
+                params![scope, kind, synced_at],
+            )
+            .map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to write activity cache: {}", e),
+                )
+            })?;
+        Ok(())
+    }
+
+    /// The login last seen signed in on `host`.
+    pub fn last_login(&self, host: &str) -> Result<Option<String>, AppError> {
+        self.conn()
+            .query_row(
+                "SELECT login FROM accounts WHERE host = ?1",
+                params![host],
+                |row| row.get(0),
+            )
+            .optional()
+            .map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to read activity cache: {}", e),
+                )
+            })
+    }
+
+    pub fn set_last_login(&self, host: &str, login: &str) -> Result<(), AppError> {
+        self.conn()
+            .execute(
+                "INSERT OR REPLACE INTO accounts (host, login) VALUES (?1, ?2)",
+                params![host, login],
+            )
+            .map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to write activity cache: {}", e),
+                )
+            })?;
+        Ok(())
+    }
+
+    /// Drops every cached record, sync marker and remembered login.
+    pub fn clear(&self) -> Result<(), AppError> {
+        self.conn()
+            .execute_batch(
+                "DELETE FROM activity; DELETE FROM coverage; DELETE FROM last_sync; DELETE FROM accounts;",
+            )
+            .map_err(|e| {
+                AppError::new(
+                    ErrorCode::Cache,
+                    format!("Failed to clear activity cache: {}", e),
+                )
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(date: &str) -> NaiveDate {
+        parse_date(date).unwrap()
+    }
+
+    #[test]
+    fn test_gaps_without_coverage() {
+        assert_eq!(
+            gaps(None, day("2024-01-01"), day("2024-01-31")),
+            vec![(day("2024-01-01"), day("2024-01-31"))]
+        );
+    }
+
+    #[test]
+    fn test_gaps_around_coverage() {
+        let covered = Some((day("2024-02-01"), day("2024-02-29")));
+
+        assert_eq!(
+            gaps(covered, day("2024-01-15"), day("2024-03-10")),
+            vec![
+                (day("2024-01-15"), day("2024-01-31")),
+                (day("2024-03-01"), day("2024-03-10"))
+            ]
+        );
+        assert!(gaps(covered, day("2024-02-05"), day("2024-02-10")).is_empty());
+    }
+
+    #[test]
+    fn test_disjoint_request_replaces_coverage() {
+        let covered = Some((day("2024-02-01"), day("2024-02-29")));
+
+        assert_eq!(
+            gaps(covered, day("2024-05-01"), day("2024-05-31")),
+            vec![(day("2024-05-01"), day("2024-05-31"))]
+        );
+        assert_eq!(
+            merge_coverage(covered, day("2024-05-01"), day("2024-05-31")),
+            (day("2024-05-01"), day("2024-05-31"))
+        );
+        assert_eq!(
+            merge_coverage(covered, day("2024-03-01"), day("2024-03-31")),
+            (day("2024-02-01"), day("2024-03-31"))
+        );
+    }
+
+    #[test]
+    fn test_clear_drops_coverage() {
+        let cache = ActivityCache::open_in_memory().unwrap();
+        cache
+            .set_coverage(
+                "github.com",
+                "authored",
+                day("2024-01-01"),
+                day("2024-01-31"),
+            )
+            .unwrap();
+
+        cache.clear().unwrap();
+
+        assert!(cache.coverage("github.com", "authored").unwrap().is_none());
+    }
+}
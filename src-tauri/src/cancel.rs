@@ -0,0 +1,200 @@
+use crate::error::{AppError, ErrorCode};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::io;
+use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use tokio::process::Command;
+use tokio::runtime::Handle;
+use tokio::sync::Notify;
+
+thread_local! {
+    /// Token of the fetch running on this blocking thread, if any.
+    static CURRENT: RefCell<Option<Arc<CancelToken>>> = const { RefCell::new(None) };
+}
+
+/// Cancellation flag shared by everything one request started.
+#[derive(Debug, Default)]
+pub struct CancelToken {
+    cancelled: AtomicBool,
+    notify: Notify,
+}
+
+impl CancelToken {
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::SeqCst);
+        self.notify.notify_waiters();
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Resolves once the token is cancelled.
+    pub async fn cancelled(&self) {
+        let notified = self.notify.notified();
+        tokio::pin!(notified);
+        notified.as_mut().enable();
+        if self.is_cancelled() {
+            return;
+        }
+        notified.await;
+    }
+}
+
+/// Requests in flight, by the request id the frontend passed in. Managed as
+/// Tauri state so `cancel_fetch` can reach them.
+///
+/// Requests sharing an id share a token, so one cancel stops all of them.
+#[derive(Debug, Default)]
+pub struct InFlight {
+    tokens: Mutex<HashMap<String, Weak<CancelToken>>>,
+}
+
+impl InFlight {
+    /// Returns the token for `request_id`, shared with anything else running under it.
+    pub fn register(&self, request_id: Option<&str>) -> Arc<CancelToken> {
+        let Some(request_id) = request_id else {
+            return Arc::default();
+        };
+
+        let mut tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        tokens.retain(|_, token| token.strong_count() > 0);
+        if let Some(token) = tokens.get(request_id).and_then(Weak::upgrade) {
+            return token;
+        }
+
+        let token = Arc::new(CancelToken::default());
+        tokens.insert(request_id.to_string(), Arc::downgrade(&token));
+        token
+    }
+
+    /// Cancels the fetches started with `request_id`. Returns `false` when none
+    /// are running.
+    pub fn cancel(&self, request_id: &str) -> bool {
+        let tokens = self.tokens.lock().unwrap_or_else(|e| e.into_inner());
+        match tokens.get(request_id).and_then(Weak::upgrade) {
+            Some(token) => {
+                token.cancel();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Runs `work` on the blocking thread pool, returning a `Cancelled` error as
+    /// soon as `request_id` is cancelled. Processes started through [`output`]
+    /// are killed on cancel and [`check`] fails, so the work winds down quickly.
+    pub async fn run<T, F>(&self, request_id: Option<String>, work: F) -> Result<T, AppError>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> T + Send + 'static,
+    {
+        let token = self.register(request_id.as_deref());
+        let task = {
+            let token = token.clone();
+            tokio::task::spawn_blocking(move || {
+                CURRENT.with(|current| *current.borrow_mut() = Some(token));
+                let result = work();
+                CURRENT.with(|current| current.borrow_mut().take());
+                result
+            })
+        };
+
+        // Biased so work that fails because it was cancelled still reports
+        // `Cancelled`, rather than whatever error the killed process produced.
+        tokio::select! {
+            biased;
+            _ = token.cancelled() => Err(AppError::cancelled()),
+            joined = task => joined.map_err(|e| {
+                AppError::new(ErrorCode::Io, format!("Fetch failed unexpectedly: {}", e))
+            }),
+        }
+    }
+}
+
+fn current() -> Option<Arc<CancelToken>> {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Fails once the fetch running on this thread has been cancelled, for loops
+/// to call between requests.
+pub fn check() -> Result<(), AppError> {
+    match current() {
+        Some(token) if token.is_cancelled() => Err(AppError::cancelled()),
+        _ => Ok(()),
+    }
+}
+
+/// Runs `command` to completion like `Command::output`, killing it if the
+/// current fetch is cancelled.
+pub fn output(command: &mut Command) -> io::Result<Output> {
+    command.kill_on_drop(true);
+    let token = current();
+    let run = async {
+        match token {
+            Some(token) => tokio::select! {
+                output = command.output() => output,
+                _ = token.cancelled() => Err(io::Error::new(io::ErrorKind::Interrupted, "cancelled")),
+            },
+            None => command.output().await,
+        }
+    };
+
+    match Handle::try_current() {
+        Ok(handle) => handle.block_on(run),
+        Err(_) => tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?
+            .block_on(run),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn test_output_without_runtime() {
+        let output = output(Command::new("echo").arg("hello")).unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hello");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_cancel_kills_running_process() {
+        let in_flight = Arc::new(InFlight::default());
+        let canceller = in_flight.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(100)).await;
+            assert!(canceller.cancel("req-1"));
+        });
+
+        let started = Instant::now();
+        let result = in_flight
+            .run(Some("req-1".to_string()), || {
+                output(Command::new("sleep").arg("10")).map(|o| o.status.success())
+            })
+            .await;
+
+        assert_eq!(result.unwrap_err().code, ErrorCode::Cancelled);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        assert!(!in_flight.cancel("unknown"));
+    }
+
+    #[test]
+    fn test_requests_sharing_an_id_share_a_token() {
+        let in_flight = InFlight::default();
+
+        let first = in_flight.register(Some("req-1"));
+        let second = in_flight.register(Some("req-1"));
+        in_flight.cancel("req-1");
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(second.is_cancelled());
+        assert!(!in_flight.register(None).is_cancelled());
+    }
+}
@@ -0,0 +1,388 @@
+use crate::PullRequest;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Estimated tokens of data (PRs, issues, reviews) that still fit in one summary
+/// prompt, for providers that don't set their own budget.
+pub const DEFAULT_PROMPT_TOKEN_BUDGET: usize = 24_000;
+/// Chunks are sized so this many of them would fit in the prompt budget.
+const CHUNKS_PER_PROMPT: usize = 3;
+/// Completion tokens for each chunk's notes.
+pub const CHUNK_MAX_TOKENS: u32 = 700;
+
+/// How pull requests are split up when there are too many to summarize in one prompt.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ChunkStrategy {
+    /// One prompt while the PRs fit, otherwise chunks by repository.
+    #[default]
+    Auto,
+    /// Always one prompt, however large.
+    Single,
+    /// Chunks by repository.
+    Repository,
+    /// Chunks by the month PRs were merged.
+    Month,
+}
+
+impl ChunkStrategy {
+    /// How the chunks are described to the model in the final pass.
+    pub fn describe(self) -> &'static str {
+        match self {
+            ChunkStrategy::Month => "month",
+            _ => "repository",
+        }
+    }
+}
+
+/// PRs summarized together in one pass.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Chunk {
+    /// The repositories or months the chunk covers, e.g. `acme/web, acme/api`.
+    pub label: String,
+    /// One formatted entry per PR.
+    pub entries: Vec<String>,
+    tokens: usize,
+}
+
+/// Rough token count of `text`, at about four characters per token.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Splits `prs`, formatted as `entries`, into the chunks to summarize
+/// separately. A single chunk means no map pass is needed.
+///
+/// `budget` is the provider's prompt token budget, and `reserved` the estimated
+/// tokens of the rest of the prompt's data (issues, reviews, the rubric), which
+/// count against it but can't be chunked. Small groups are packed together up
+/// to the chunk budget, and groups over it are split, so the number of passes
+/// tracks the size of the data rather than the number of repositories or months.
+pub fn plan(
+    prs: &[PullRequest],
+    entries: &[String],
+    strategy: ChunkStrategy,
+    budget: usize,
+    reserved: usize,
+) -> Vec<Chunk> {
+    let total: usize = entries.iter().map(|e| estimate_tokens(e)).sum();
+    let chunk_budget = (budget / CHUNKS_PER_PROMPT).max(1);
+    let key: fn(&PullRequest) -> String = match strategy {
+        ChunkStrategy::Auto if total + reserved <= budget => return single(entries, total),
+        ChunkStrategy::Single => return single(entries, total),
+        ChunkStrategy::Auto | ChunkStrategy::Repository => {
+            |pr| pr.repository.name_with_owner.clone()
+        }
+        ChunkStrategy::Month => |pr| pr.closed_at.get(..7).unwrap_or(&pr.closed_at).to_string(),
+    };
+
+    let mut groups: BTreeMap<String, Vec<&String>> = BTreeMap::new();
+    for (pr, entry) in prs.iter().zip(entries) {
+        groups.entry(key(pr)).or_default().push(entry);
+    }
+
+    let mut pieces = Vec::new();
+    for (key, entries) in groups {
+        let slices = split_by_budget(entries, chunk_budget);
+        let count = slices.len();
+        for (i, slice) in slices.into_iter().enumerate() {
+            let label = if count > 1 {
+                format!("{} ({}/{})", key, i + 1, count)
+            } else {
+                key.clone()
+            };
+            pieces.push(Chunk { label, ..slice });
+        }
+    }
+
+    let mut chunks: Vec<Chunk> = Vec::new();
+    for piece in pieces {
+        if let Some(last) = chunks.last_mut()
+            && last.tokens + piece.tokens <= chunk_budget
+        {
+            last.label = format!("{}, {}", last.label, piece.label);
+            last.entries.extend(piece.entries);
+            last.tokens += piece.tokens;
+        } else {
+            chunks.push(piece);
+        }
+    }
+    chunks
+}
+
+fn single(entries: &[String], tokens: usize) -> Vec<Chunk> {
+    vec![Chunk {
+        label: String::new(),
+        entries: entries.to_vec(),
+        tokens,
+    }]
+}
+
+/// Splits one group's entries into unlabelled slices within `chunk_budget`.
+fn split_by_budget(entries: Vec<&String>, chunk_budget: usize) -> Vec<Chunk> {
+    let mut slices: Vec<Chunk> = Vec::new();
+    for entry in entries {
+        let tokens = estimate_tokens(entry);
+        match slices.last_mut() {
+            Some(last) if last.tokens + tokens <= chunk_budget => {
+                last.entries.push(entry.clone());
+                last.tokens += tokens;
+            }
+            _ => slices.push(Chunk {
+                label: String::new(),
+                entries: vec![entry.clone()],
+                tokens,
+            }),
+        }
+    }
+    slices
+}
+
+/// Prompt asking for factual notes on one chunk, for the final pass to build on.
+pub fn chunk_prompt(chunk: &Chunk, date_range: &str, org_name: &str) -> String {
+    format!(
+        r#"You are helping an engineer prepare a performance review self-assessment for {} at {}. Their merged pull requests are being summarized in parts; this part covers {}.
+
+Write concise notes on this part of their work:
+- The main features, fixes and improvements, naming the PRs they come from (with their URLs, where listed)
+- Themes that connect several PRs (e.g. incidents, performance, security, migrations)
+- Evidence of collaboration or leadership, only where the PRs show it
+
+NEVER invent metrics, percentages or outcomes the PRs don't show. Use plain bullet points, no headings.
+
+Here are the {} merged pull requests:
+
+{}"#,
+        date_range,
+        org_name,
+        chunk.label,
+        chunk.entries.len(),
+        chunk.entries.join("\n\n")
+    )
+}
+
+/// A chunk's notes as they are listed in the prompt.
+pub fn format_note(label: &str, text: &str) -> String {
+    format!("### {}\n\n{}", label, text)
+}
+
+/// Batches of `notes` (label and text) to condense into one note each, when
+/// together with `reserved` they still don't fit in `budget`. `None` once they
+/// fit, or when a single note is left.
+///
+/// Batches are packed up to the chunk budget but always take at least two
+/// notes, so every pass leaves fewer of them.
+pub fn reduce_plan(
+    notes: &[(String, String)],
+    budget: usize,
+    reserved: usize,
+) -> Option<Vec<Chunk>> {
+    let entries: Vec<String> = notes
+        .iter()
+        .map(|(label, text)| format_note(label, text))
+        .collect();
+    let total: usize = entries.iter().map(|e| estimate_tokens(e)).sum();
+    if notes.len() < 2 || total + reserved <= budget {
+        return None;
+    }
+
+    let chunk_budget = (budget / CHUNKS_PER_PROMPT).max(1);
+    let mut batches: Vec<Chunk> = Vec::new();
+    for ((label, _), entry) in notes.iter().zip(entries) {
+        let tokens = estimate_tokens(&entry);
+        match batches.last_mut() {
+            Some(last) if last.entries.len() < 2 || last.tokens + tokens <= chunk_budget => {
+                last.label = format!("{}, {}", last.label, label);
+                last.entries.push(entry);
+                last.tokens += tokens;
+            }
+            _ => batches.push(Chunk {
+                label: label.clone(),
+                entries: vec![entry],
+                tokens,
+            }),
+        }
+    }
+    Some(batches)
+}
+
+/// Prompt asking to condense a batch of chunk notes into one set of notes.
+pub fn reduce_prompt(batch: &Chunk, date_range: &str, org_name: &str) -> String {
+    format!(
+        r#"You are helping an engineer prepare a performance review self-assessment for {} at {}. Their merged pull requests were summarized in parts, and there are too many notes to use at once; these cover {}.
+
+Combine these notes into one shorter set of notes:
+- Keep the most significant features, fixes and improvements, with the PRs (and URLs) they come from
+- Merge themes that appear in several parts
+- Keep evidence of collaboration or leadership
+
+NEVER invent metrics, percentages or outcomes the notes don't show. Use plain bullet points, no headings.
+
+Here are the notes on {} parts:
+
+{}"#,
+        date_range,
+        org_name,
+        batch.label,
+        batch.entries.len(),
+        batch.entries.join("\n\n")
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Repository;
+
+    fn pr(repo: &str, closed_at: &str) -> PullRequest {
+        PullRequest {
+            title: "Change".to_string(),
+            url: String::new(),
+            body: None,
+            closed_at: closed_at.to_string(),
+            created_at: None,
+            number: None,
+            repository: Repository {
+                name: repo.to_string(),
+                name_with_owner: format!("acme/{}", repo),
+                host: None,
+            },
+            size: None,
+            labels: Vec::new(),
+            milestone: None,
+            closing_issues: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_auto_keeps_small_sets_in_one_prompt() {
+        let prs = vec![pr("web", "2024-01-05"), pr("api", "2024-02-05")];
+        let entries = vec!["- web".to_string(), "- api".to_string()];
+
+        let chunks = plan(
+            &prs,
+            &entries,
+            ChunkStrategy::Auto,
+            DEFAULT_PROMPT_TOKEN_BUDGET,
+            0,
+        );
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].entries, entries);
+    }
+
+    #[test]
+    fn test_auto_counts_the_rest_of_the_prompt_against_the_budget() {
+        let prs = vec![pr("web", "2024-01-05"), pr("api", "2024-02-05")];
+        let entries = vec!["x".repeat(4_000), "y".repeat(4_000)];
+
+        let roomy = plan(&prs, &entries, ChunkStrategy::Auto, 4_000, 1_000);
+        let crowded = plan(&prs, &entries, ChunkStrategy::Auto, 4_000, 3_000);
+
+        assert_eq!(roomy.len(), 1);
+        assert_eq!(crowded.len(), 2);
+        assert!(
+            crowded
+                .iter()
+                .all(|c| c.tokens <= 4_000 / CHUNKS_PER_PROMPT)
+        );
+    }
+
+    #[test]
+    fn test_large_sets_are_chunked_by_repository() {
+        let prs: Vec<PullRequest> = (0..60)
+            .map(|i| pr(if i % 3 == 0 { "api" } else { "web" }, "2024-01-05"))
+            .collect();
+        let entries: Vec<String> = (0..60)
+            .map(|i| format!("{}{}", i, "x".repeat(2_000)))
+            .collect();
+
+        let chunks = plan(
+            &prs,
+            &entries,
+            ChunkStrategy::Auto,
+            DEFAULT_PROMPT_TOKEN_BUDGET,
+            0,
+        );
+        let labels: Vec<&str> = chunks.iter().map(|c| c.label.as_str()).collect();
+
+        assert_eq!(
+            labels,
+            vec![
+                "acme/api (1/2)",
+                "acme/api (2/2)",
+                "acme/web (1/3)",
+                "acme/web (2/3)",
+                "acme/web (3/3)"
+            ]
+        );
+        assert_eq!(chunks.iter().map(|c| c.entries.len()).sum::<usize>(), 60);
+        assert!(
+            chunks
+                .iter()
+                .all(|c| c.tokens <= DEFAULT_PROMPT_TOKEN_BUDGET / CHUNKS_PER_PROMPT)
+        );
+    }
+
+    #[test]
+    fn test_month_strategy_packs_small_months() {
+        let prs = vec![
+            pr("web", "2024-03-01T10:00:00Z"),
+            pr("web", "2024-01-15T10:00:00Z"),
+        ];
+        let entries = vec!["- march".to_string(), "- january".to_string()];
+
+        let chunks = plan(
+            &prs,
+            &entries,
+            ChunkStrategy::Month,
+            DEFAULT_PROMPT_TOKEN_BUDGET,
+            0,
+        );
+
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(chunks[0].label, "2024-01, 2024-03");
+        assert_eq!(chunks[0].entries, vec!["- january", "- march"]);
+    }
+
+    #[test]
+    fn test_notes_over_the_budget_are_reduced_until_they_fit() {
+        let notes: Vec<(String, String)> = (0..12)
+            .map(|i| (format!("acme/repo{}", i), "x".repeat(2_800)))
+            .collect();
+
+        let first = reduce_plan(&notes, 6_000, 2_000).unwrap();
+        let condensed: Vec<(String, String)> = first
+            .iter()
+            .map(|batch| (batch.label.clone(), "x".repeat(2_800)))
+            .collect();
+        let second = reduce_plan(&condensed, 6_000, 2_000).unwrap();
+        let reduced: Vec<(String, String)> = second
+            .iter()
+            .map(|batch| (batch.label.clone(), "x".repeat(2_800)))
+            .collect();
+
+        assert_eq!(first.len(), 6);
+        assert_eq!(first[0].label, "acme/repo0, acme/repo1");
+        assert_eq!(first.iter().map(|b| b.entries.len()).sum::<usize>(), 12);
+        assert_eq!(second.len(), 3);
+        assert_eq!(
+            second[0].label,
+            "acme/repo0, acme/repo1, acme/repo2, acme/repo3"
+        );
+        assert!(reduce_plan(&reduced, 6_000, 2_000).is_none());
+    }
+
+    #[test]
+    fn test_a_single_note_is_never_reduced() {
+        let notes = vec![("acme/web".to_string(), "x".repeat(40_000))];
+
+        assert!(reduce_plan(&notes, 6_000, 0).is_none());
+    }
+
+    #[test]
+    fn test_estimate_tokens() {
+        assert_eq!(estimate_tokens(""), 0);
+        assert_eq!(estimate_tokens("abcde"), 2);
+    }
+}
@@ -0,0 +1,385 @@
+use crate::cancel;
+use crate::error::{AppError, ErrorCode};
+use crate::{AppSettings, gh_path};
+use chrono::DateTime;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use tokio::process::Command;
+
+/// The rate limit buckets the app draws from.
+const RATE_LIMIT_RESOURCES: [&str; 3] = ["core", "search", "graphql"];
+
+/// An account listed by `gh auth status`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GhAccount {
+    pub host: String,
+    pub login: String,
+    /// Whether this is the account `gh` uses for the host.
+    pub active: bool,
+    /// `false` when the stored token was rejected.
+    pub authenticated: bool,
+    pub scopes: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RateLimit {
+    pub host: String,
+    pub resource: String,
+    pub limit: u32,
+    pub remaining: u32,
+    #[serde(rename = "resetAt")]
+    pub reset_at: Option<String>,
+}
+
+/// Everything onboarding needs to go right, checked in one pass.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EnvironmentReport {
+    #[serde(rename = "ghPath")]
+    pub gh_path: Option<String>,
+    #[serde(rename = "ghVersion")]
+    pub gh_version: Option<String>,
+    pub accounts: Vec<GhAccount>,
+    #[serde(rename = "rateLimits")]
+    pub rate_limits: Vec<RateLimit>,
+    #[serde(rename = "dataDir")]
+    pub data_dir: String,
+    #[serde(rename = "dataDirWritable")]
+    pub data_dir_writable: bool,
+    #[serde(rename = "aiKeyConfigured")]
+    pub ai_key_configured: bool,
+    /// Every check that failed, in the order they ran.
+    pub problems: Vec<AppError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitResponse {
+    resources: BTreeMap<String, RateLimitBucket>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RateLimitBucket {
+    limit: u32,
+    remaining: u32,
+    reset: Option<i64>,
+}
+
+/// Runs `gh` with `args` and returns its combined output, or its stderr as an error.
+fn run_gh(gh: &Path, host: Option<&str>, args: &[&str]) -> Result<String, AppError> {
+    let mut command = Command::new(gh);
+    if let Some(host) = host {
+        command.env("GH_HOST", host);
+    }
+    let output = cancel::output(command.args(args)).map_err(|e| {
+        AppError::new(
+            ErrorCode::Io,
+            format!("Failed to execute GitHub CLI: {}", e),
+        )
+    })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if output.status.success() {
+        Ok(format!("{}{}", stdout, stderr))
+    } else {
+        Err(AppError::from_gh_stderr(&format!("{}{}", stderr, stdout)))
+    }
+}
+
+/// Extracts the version number from `gh --version`.
+fn parse_version(output: &str) -> Option<String> {
+    output
+        .lines()
+        .next()?
+        .strip_prefix("gh version ")?
+        .split_whitespace()
+        .next()
+        .map(|v| v.to_string())
+}
+
+/// Reads the accounts out of `gh auth status`, in both the per-account format
+/// (gh 2.40 and later) and the older one-account-per-host format.
+fn parse_auth_status(output: &str) -> Vec<GhAccount> {
+    let mut accounts: Vec<GhAccount> = Vec::new();
+
+    for line in output.lines() {
+        let line = line.trim().trim_start_matches(['✓', 'X', '-', '!', ' ']);
+
+        let (rest, authenticated) = if let Some(rest) = line.strip_prefix("Logged in to ") {
+            (rest, true)
+        } else if let Some(rest) = line.strip_prefix("Failed to log in to ") {
+            (rest, false)
+        } else {
+            if let Some(account) = accounts.last_mut() {
+                if let Some(active) = line.strip_prefix("Active account: ") {
+                    account.active = active.trim() == "true";
+                } else if let Some(scopes) = line.strip_prefix("Token scopes: ") {
+                    account.scopes = scopes
+                        .split(',')
+                        .map(|s| s.trim().trim_matches('\'').to_string())
+                        .filter(|s| !s.is_empty() && s != "none")
+                        .collect();
+                }
+            }
+            continue;
+        };
+
+        let mut words = rest.split_whitespace();
+        let host = words.next().unwrap_or_default();
+        let login = match words.next() {
+            Some("account") | Some("as") => words.next().unwrap_or_default(),
+            _ => "",
+        };
+
+        accounts.push(GhAccount {
+            host: host.to_string(),
+            login: login.to_string(),
+            active: true,
+            authenticated,
+            scopes: Vec::new(),
+        });
+    }
+
+    accounts
+}
+
+fn parse_rate_limits(host: &str, output: &str) -> Result<Vec<RateLimit>, AppError> {
+    let response: RateLimitResponse = serde_json::from_str(output).map_err(|e| {
+        AppError::new(
+            ErrorCode::Parse,
+            format!("Failed to parse rate limit response: {}", e),
+        )
+    })?;
+
+    Ok(RATE_LIMIT_RESOURCES
+        .iter()
+        .filter_map(|resource| {
+            let bucket = response.resources.get(*resource)?;
+            Some(RateLimit {
+                host: host.to_string(),
+                resource: resource.to_string(),
+                limit: bucket.limit,
+                remaining: bucket.remaining,
+                reset_at: bucket
+                    .reset
+                    .and_then(|reset| DateTime::from_timestamp(reset, 0))
+                    .map(|reset| reset.to_rfc3339()),
+            })
+        })
+        .collect())
+}
+
+/// Checks that files can be created in `dir` by writing and removing a probe file.
+fn check_writable(dir: &Path) -> Result<(), AppError> {
+    let probe = dir.join(".write-check");
+    fs::write(&probe, b"ok")
+        .and_then(|_| fs::remove_file(&probe))
+        .map_err(|e| {
+            AppError::new(
+                ErrorCode::Io,
+                format!("Data directory {} is not writable: {}", dir.display(), e),
+            )
+        })
+}
+
+/// Checks the `gh` installation and login, the data directory and the AI key.
+pub fn check(data_dir: &Path, settings: &AppSettings) -> EnvironmentReport {
+    let mut problems = Vec::new();
+    let mut gh_version = None;
+    let mut accounts = Vec::new();
+    let mut rate_limits = Vec::new();
+
+    let gh_path = gh_path::locate(settings.gh_path.as_deref());
+    match &gh_path {
+        Err(e) => problems.push(e.clone()),
+        Ok(gh) => {
+            match run_gh(gh, None, &["--version"]) {
+                Ok(output) => gh_version = parse_version(&output),
+                Err(e) => problems.push(e),
+            }
+
+            // `gh auth status` exits non-zero when any account is broken, but
+            // still lists every account, so parse its output either way.
+            let status = match run_gh(gh, None, &["auth", "status"]) {
+                Ok(output) => output,
+                Err(e) => e.message,
+            };
+            accounts = parse_auth_status(&status);
+
+            if accounts.is_empty() {
+                problems.push(AppError::new(
+                    ErrorCode::NotAuthenticated,
+                    "GitHub CLI is not logged in to any host. Run `gh auth login`.",
+                ));
+            }
+
+            for account in &accounts {
+                if !account.authenticated {
+                    problems.push(
+                        AppError::new(
+                            ErrorCode::NotAuthenticated,
+                            format!(
+                                "The token for {} is invalid. Run `gh auth login -h {}`.",
+                                account.login, account.host
+                            ),
+                        )
+                        .with_host(&account.host),
+                    );
+                } else if account.active {
+                    match run_gh(gh, Some(&account.host), &["api", "rate_limit"])
+                        .and_then(|output| parse_rate_limits(&account.host, &output))
+                    {
+                        Ok(limits) => rate_limits.extend(limits),
+                        Err(e) => problems.push(e.with_host(&account.host)),
+                    }
+                }
+            }
+
+            for limit in rate_limits.iter().filter(|l| l.remaining == 0) {
+                problems.push(
+                    AppError::new(
+                        ErrorCode::RateLimited,
+                        format!("The {} API rate limit is exhausted", limit.resource),
+                    )
+                    .with_host(&limit.host),
+                );
+            }
+        }
+    }
+
+    let data_dir_writable = match check_writable(data_dir) {
+        Ok(()) => true,
+        Err(e) => {
+            problems.push(e);
+            false
+        }
+    };
+
+    let ai_key_configured = settings
+        .api_key
+        .as_deref()
+        .is_some_and(|key| !key.trim().is_empty());
+    if !ai_key_configured && settings.ai_provider.requires_api_key() {
+        problems.push(AppError::new(
+            ErrorCode::Config,
+            "No AI API key is configured; AI summaries are unavailable",
+        ));
+    }
+
+    EnvironmentReport {
+        gh_path: gh_path.ok().map(|p| p.display().to_string()),
+        gh_version,
+        accounts,
+        rate_limits,
+        data_dir: data_dir.display().to_string(),
+        data_dir_writable,
+        ai_key_configured,
+        problems,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(
+            parse_version(
+                "gh version 2.40.1 (2023-12-13)\nhttps://github.com/cli/cli/releases/tag/v2.40.1\n"
+            ),
+            Some("2.40.1".to_string())
+        );
+        assert_eq!(parse_version("something else"), None);
+    }
+
+    #[test]
+    fn test_parse_auth_status() {
+        let output = "github.com
+  ✓ Logged in to github.com account octocat (keyring)
+  - Active account: true
+  - Git operations protocol: https
+  - Token: gho_************************************
+  - Token scopes: 'gist', 'read:org', 'repo'
+
+  ✓ Logged in to github.com account octo-work (keyring)
+  - Active account: false
+  - Token scopes: 'repo'
+
+ghe.example.com
+  X Failed to log in to ghe.example.com account octocat (GH_ENTERPRISE_TOKEN)
+  - Active account: true
+  - The token in GH_ENTERPRISE_TOKEN is invalid.
+";
+
+        let accounts = parse_auth_status(output);
+
+        assert_eq!(accounts.len(), 3);
+        assert_eq!(accounts[0].login, "octocat");
+        assert!(accounts[0].active && accounts[0].authenticated);
+        assert_eq!(accounts[0].scopes, vec!["gist", "read:org", "repo"]);
+        assert!(!accounts[1].active);
+        assert_eq!(accounts[2].host, "ghe.example.com");
+        assert!(!accounts[2].authenticated);
+    }
+
+    #[test]
+    fn test_parse_legacy_auth_status() {
+        let output = "github.com
+  ✓ Logged in to github.com as octocat (/home/me/.config/gh/hosts.yml)
+  ✓ Git operations for github.com configured to use https protocol.
+  ✓ Token: *******************
+  ✓ Token scopes: gist, read:org, repo
+";
+
+        let accounts = parse_auth_status(output);
+
+        assert_eq!(
+            accounts,
+            vec![GhAccount {
+                host: "github.com".to_string(),
+                login: "octocat".to_string(),
+                active: true,
+                authenticated: true,
+                scopes: vec![
+                    "gist".to_string(),
+                    "read:org".to_string(),
+                    "repo".to_string()
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn test_parse_rate_limits() {
+        let output = r#"{"resources": {
+            "core": {"limit": 5000, "used": 1, "remaining": 4999, "reset": 1700000000},
+            "search": {"limit": 30, "used": 30, "remaining": 0, "reset": 1700000060},
+            "graphql": {"limit": 5000, "used": 0, "remaining": 5000, "reset": 1700003600},
+            "integration_manifest": {"limit": 5000, "used": 0, "remaining": 5000, "reset": 1700003600}
+        }}"#;
+
+        let limits = parse_rate_limits("github.com", output).unwrap();
+
+        assert_eq!(limits.len(), 3);
+        assert_eq!(limits[1].resource, "search");
+        assert_eq!(limits[1].remaining, 0);
+        assert_eq!(
+            limits[0].reset_at.as_deref(),
+            Some("2023-11-14T22:13:20+00:00")
+        );
+    }
+
+    #[test]
+    fn test_check_writable() {
+        let dir = std::env::temp_dir();
+        assert!(check_writable(&dir).is_ok());
+        assert_eq!(
+            check_writable(&dir.join("missing-dir-for-write-check"))
+                .unwrap_err()
+                .code,
+            ErrorCode::Io
+        );
+    }
+}
@@ -0,0 +1,234 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// What went wrong, so the frontend can offer a targeted fix.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// The GitHub CLI couldn't be found; the fix is installing it.
+    GhNotInstalled,
+    /// Missing or rejected credentials; the fix is `gh auth login` or a new token.
+    NotAuthenticated,
+    /// The API refused the request because of its rate limit; see `retry_after`.
+    RateLimited,
+    /// The service couldn't be reached at all.
+    Network,
+    /// The service answered with an error.
+    Api,
+    /// A response or file couldn't be parsed.
+    Parse,
+    /// Reading or writing a local file or running a local tool failed.
+    Io,
+    /// The local activity cache failed.
+    Cache,
+    /// Settings are missing something the command needs.
+    Config,
+    /// The command was called with arguments it can't work with.
+    InvalidInput,
+    /// The request was cancelled by the user.
+    Cancelled,
+}
+
+/// The error every command reports: a machine-readable code, a message for
+/// people, and whatever structured details are known.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AppError {
+    pub code: ErrorCode,
+    pub message: String,
+    /// Host the error came from, when activity is fetched from several.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
+    /// HTTP status of the failed request.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<u16>,
+    /// Seconds to wait before retrying, for rate limits.
+    #[serde(
+        default,
+        rename = "retryAfter",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub retry_after: Option<u64>,
+}
+
+impl AppError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            host: None,
+            status: None,
+            retry_after: None,
+        }
+    }
+
+    pub fn gh_not_installed() -> Self {
+        Self::new(
+            ErrorCode::GhNotInstalled,
+            "GitHub CLI not found. Please install it from https://cli.github.com",
+        )
+    }
+
+    pub fn cancelled() -> Self {
+        Self::new(ErrorCode::Cancelled, "The request was cancelled")
+    }
+
+    pub fn with_host(mut self, host: &str) -> Self {
+        if self.host.is_none() {
+            self.host = Some(host.to_string());
+        }
+        self
+    }
+
+    /// Classifies an error printed by `gh`.
+    pub fn from_gh_stderr(stderr: &str) -> Self {
+        let stderr = stderr.trim();
+        let lower = stderr.to_lowercase();
+
+        let code = if lower.contains("gh auth login")
+            || lower.contains("not logged in")
+            || lower.contains("bad credentials")
+            || lower.contains("http 401")
+        {
+            ErrorCode::NotAuthenticated
+        } else if lower.contains("rate limit") {
+            ErrorCode::RateLimited
+        } else if lower.contains("error connecting") || lower.contains("no such host") {
+            ErrorCode::Network
+        } else {
+            ErrorCode::Api
+        };
+
+        Self::new(code, format!("GitHub CLI error: {}", stderr))
+    }
+
+    /// Classifies a non-success HTTP response from `service`.
+    ///
+    /// `retry_after` is the `Retry-After` header, or the seconds until the
+    /// `X-RateLimit-Reset` time, when the response carries either.
+    pub fn from_http(
+        service: &str,
+        status: u16,
+        retry_after: Option<u64>,
+        rate_limit_exhausted: bool,
+        body: &str,
+    ) -> Self {
+        let code = match status {
+            401 => ErrorCode::NotAuthenticated,
+            429 => ErrorCode::RateLimited,
+            403 if rate_limit_exhausted || body.to_lowercase().contains("rate limit") => {
+                ErrorCode::RateLimited
+            }
+            _ => ErrorCode::Api,
+        };
+
+        Self {
+            status: Some(status),
+            retry_after: retry_after.filter(|_| code == ErrorCode::RateLimited),
+            ..Self::new(code, format!("{} error ({}): {}", service, status, body))
+        }
+    }
+
+    /// Classifies a failed HTTP request to `service`.
+    pub fn from_request(service: &str, error: reqwest::Error) -> Self {
+        let code = if error.is_decode() {
+            ErrorCode::Parse
+        } else {
+            ErrorCode::Network
+        };
+
+        Self::new(code, format!("Failed to call {}: {}", service, error))
+    }
+
+    /// Classifies an HTTP response by its status and rate limit headers.
+    pub fn from_response_parts(
+        service: &str,
+        status: reqwest::StatusCode,
+        headers: &reqwest::header::HeaderMap,
+        body: &str,
+    ) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.trim().parse::<u64>().ok())
+        };
+        let retry_after = header("retry-after").or_else(|| {
+            header("x-ratelimit-reset").map(|reset| {
+                let now = std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .map(|d| d.as_secs())
+                    .unwrap_or_default();
+                reset.saturating_sub(now)
+            })
+        });
+        let exhausted = header("x-ratelimit-remaining") == Some(0);
+
+        Self::from_http(service, status.as_u16(), retry_after, exhausted, body)
+    }
+}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.host {
+            Some(host) => write!(f, "{}: {}", host, self.message),
+            None => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for AppError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classifies_gh_errors() {
+        assert_eq!(
+            AppError::from_gh_stderr("To get started with GitHub CLI, please run:  gh auth login")
+                .code,
+            ErrorCode::NotAuthenticated
+        );
+        assert_eq!(
+            AppError::from_gh_stderr("API rate limit exceeded for user ID 1.").code,
+            ErrorCode::RateLimited
+        );
+        assert_eq!(
+            AppError::from_gh_stderr("Invalid search query").code,
+            ErrorCode::Api
+        );
+    }
+
+    #[test]
+    fn test_classifies_http_errors() {
+        let rate_limited = AppError::from_http("GitHub API", 403, Some(60), true, "");
+        assert_eq!(rate_limited.code, ErrorCode::RateLimited);
+        assert_eq!(rate_limited.retry_after, Some(60));
+
+        let forbidden = AppError::from_http("GitHub API", 403, Some(60), false, "Forbidden");
+        assert_eq!(forbidden.code, ErrorCode::Api);
+        assert_eq!(forbidden.retry_after, None);
+
+        assert_eq!(
+            AppError::from_http("GitLab API", 401, None, false, "").code,
+            ErrorCode::NotAuthenticated
+        );
+    }
+
+    #[test]
+    fn test_serialization() {
+        let error = AppError {
+            retry_after: Some(30),
+            ..AppError::new(ErrorCode::RateLimited, "Slow down")
+        }
+        .with_host("github.com");
+
+        let json = serde_json::to_string(&error).unwrap();
+
+        assert_eq!(
+            json,
+            r#"{"code":"rate_limited","message":"Slow down","host":"github.com","retryAfter":30}"#
+        );
+        assert_eq!(error.to_string(), "github.com: Slow down");
+    }
+}
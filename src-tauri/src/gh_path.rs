@@ -0,0 +1,158 @@
+use crate::error::{AppError, ErrorCode};
+use std::env;
+use std::ffi::OsStr;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+#[cfg(windows)]
+const GH_BINARY: &str = "gh.exe";
+#[cfg(not(windows))]
+const GH_BINARY: &str = "gh";
+
+/// Directories `gh` is commonly installed to. Apps launched from the desktop
+/// often get a minimal `PATH`, so these are checked after `PATH` itself.
+fn well_known_dirs(home: Option<&Path>) -> Vec<PathBuf> {
+    let mut dirs: Vec<PathBuf> = [
+        "/opt/homebrew/bin",
+        "/usr/local/bin",
+        "/usr/bin",
+        "/opt/local/bin",
+        "/home/linuxbrew/.linuxbrew/bin",
+        "/nix/var/nix/profiles/default/bin",
+        "/run/current-system/sw/bin",
+        "/snap/bin",
+        r"C:\Program Files\GitHub CLI",
+    ]
+    .iter()
+    .map(PathBuf::from)
+    .collect();
+
+    if let Some(home) = home {
+        for dir in [
+            ".linuxbrew/bin",
+            ".nix-profile/bin",
+            ".asdf/shims",
+            ".local/bin",
+            "bin",
+        ] {
+            dirs.push(home.join(dir));
+        }
+    }
+
+    dirs
+}
+
+fn is_executable(path: &Path) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        path.metadata()
+            .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+    }
+    #[cfg(not(unix))]
+    {
+        path.is_file()
+    }
+}
+
+/// Finds `gh` in the directories of a `PATH`-style list, then in `fallback_dirs`.
+fn search(path_var: Option<&OsStr>, fallback_dirs: &[PathBuf]) -> Option<PathBuf> {
+    path_var
+        .into_iter()
+        .flat_map(env::split_paths)
+        .chain(fallback_dirs.iter().cloned())
+        .map(|dir| dir.join(GH_BINARY))
+        .find(|candidate| is_executable(candidate))
+}
+
+/// Searches `PATH` and the well-known directories once per process.
+fn discovered() -> Option<&'static PathBuf> {
+    static DISCOVERED: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+    DISCOVERED
+        .get_or_init(|| {
+            let path_var = env::var_os("PATH");
+            search(
+                path_var.as_deref(),
+                &well_known_dirs(dirs::home_dir().as_deref()),
+            )
+        })
+        .as_ref()
+}
+
+/// Resolves the `gh` binary: the `gh_path` setting when set, otherwise the
+/// first `gh` found on `PATH` or in a well-known install location.
+pub fn locate(configured: Option<&str>) -> Result<PathBuf, AppError> {
+    if let Some(configured) = configured.map(str::trim).filter(|p| !p.is_empty()) {
+        let path = PathBuf::from(configured);
+        return if is_executable(&path) {
+            Ok(path)
+        } else {
+            Err(AppError::new(
+                ErrorCode::Config,
+                format!(
+                    "The configured gh_path {} is not an executable file",
+                    path.display()
+                ),
+            ))
+        };
+    }
+
+    discovered().cloned().ok_or_else(AppError::gh_not_installed)
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    fn fake_gh(dir: &Path, mode: u32) -> PathBuf {
+        fs::create_dir_all(dir).unwrap();
+        let gh = dir.join(GH_BINARY);
+        fs::write(&gh, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&gh, fs::Permissions::from_mode(mode)).unwrap();
+        gh
+    }
+
+    #[test]
+    fn test_search_walks_path_before_fallbacks() {
+        let root = env::temp_dir().join(format!("gh-path-search-{}", std::process::id()));
+        let not_executable = root.join("plain");
+        let on_path = root.join("nix");
+        let fallback = root.join("linuxbrew");
+        fake_gh(&not_executable, 0o644);
+        let expected = fake_gh(&on_path, 0o755);
+        let fallback_gh = fake_gh(&fallback, 0o755);
+
+        let path_var = env::join_paths([root.join("missing"), not_executable, on_path]).unwrap();
+        let found = search(Some(&path_var), std::slice::from_ref(&fallback));
+        let from_fallback = search(None, &[fallback]);
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(found, Some(expected));
+        assert_eq!(from_fallback, Some(fallback_gh));
+    }
+
+    #[test]
+    fn test_configured_path_must_be_executable() {
+        let root = env::temp_dir().join(format!("gh-path-configured-{}", std::process::id()));
+        let gh = fake_gh(&root, 0o755);
+
+        let found = locate(gh.to_str());
+        let missing = locate(Some("/nonexistent/gh"));
+        fs::remove_dir_all(&root).unwrap();
+
+        assert_eq!(found.unwrap(), gh);
+        assert_eq!(missing.unwrap_err().code, ErrorCode::Config);
+    }
+
+    #[test]
+    fn test_well_known_dirs_include_home_installs() {
+        let dirs = well_known_dirs(Some(Path::new("/home/me")));
+
+        assert!(dirs.contains(&PathBuf::from("/home/linuxbrew/.linuxbrew/bin")));
+        assert!(dirs.contains(&PathBuf::from("/home/me/.nix-profile/bin")));
+        assert!(dirs.contains(&PathBuf::from("/snap/bin")));
+    }
+}
@@ -0,0 +1,82 @@
+use serde::{Deserialize, Serialize};
+
+/// Language AI summaries are written in, named by the UI locale.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub enum Language {
+    #[default]
+    #[serde(rename = "en-US")]
+    EnUs,
+    #[serde(rename = "pt-BR")]
+    PtBr,
+}
+
+impl Language {
+    /// The language's name, as given to the model.
+    pub fn name(self) -> &'static str {
+        match self {
+            Language::EnUs => "English",
+            Language::PtBr => "Brazilian Portuguese (pt-BR)",
+        }
+    }
+
+    /// Headings of the default template's sections, in order.
+    pub fn headings(self) -> [&'static str; 5] {
+        match self {
+            Language::EnUs => [
+                "Impact Summary",
+                "Key Achievements",
+                "Technical Growth",
+                "Collaboration & Leadership",
+                "Recommended Talking Points",
+            ],
+            Language::PtBr => [
+                "Resumo de Impacto",
+                "Principais Conquistas",
+                "Crescimento Técnico",
+                "Colaboração e Liderança",
+                "Pontos de Destaque Recomendados",
+            ],
+        }
+    }
+
+    /// Prompt section asking for the summary in this language, for the
+    /// `language_section` placeholder. Empty for English, which templates are written in.
+    ///
+    /// The template's own headings are translated; `headings` names them exactly
+    /// when they are known, as for the built-in default template.
+    pub fn prompt_section(self, headings: Option<&[&str]>) -> String {
+        if self == Language::EnUs {
+            return String::new();
+        }
+        let headings = match headings {
+            Some(headings) => format!(", which must be: {}", headings.join(", ")),
+            None => " the template asks for, translated".to_string(),
+        };
+        format!(
+            "\n\nWrite the entire summary in {}, including the section headings{}. Keep PR titles, repository names and code identifiers as they are.",
+            self.name(),
+            headings
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_section() {
+        let section = Language::PtBr.prompt_section(Some(&Language::PtBr.headings()));
+        let generic = Language::PtBr.prompt_section(None);
+
+        assert_eq!(Language::EnUs.prompt_section(None), "");
+        assert!(section.contains("in Brazilian Portuguese (pt-BR)"));
+        assert!(section.contains("Resumo de Impacto, Principais Conquistas"));
+        assert!(generic.contains("section headings the template asks for, translated"));
+        assert!(!generic.contains("Resumo de Impacto"));
+        assert_eq!(
+            serde_json::from_str::<Language>(r#""pt-BR""#).unwrap(),
+            Language::PtBr
+        );
+    }
+}
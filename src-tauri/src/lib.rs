@@ -1,40 +1,51 @@
+mod cache;
+mod cancel;
+mod chunking;
+mod environment;
+mod error;
+mod gh_path;
+mod language;
+mod llm;
+mod progress;
+mod rubric;
+mod search;
+mod sources;
+mod sse;
+mod stats;
+mod structured;
+mod templates;
+
+use cache::ActivityCache;
+use cancel::{CancelToken, InFlight};
+use chunking::{CHUNK_MAX_TOKENS, ChunkStrategy};
+use environment::EnvironmentReport;
+use error::{AppError, ErrorCode};
+use language::Language;
+use llm::{AnthropicProvider, LlmProvider, OllamaProvider, OpenAiProvider};
+use progress::{
+    AI_CHUNK_EVENT, AI_PROGRESS_EVENT, AiChunk, AiPhase, AiProgress, FETCH_PROGRESS_EVENT,
+};
+use rubric::{CompetencyEvidence, MAPPING_BATCH_SIZE, MAPPING_MAX_TOKENS};
 use serde::{Deserialize, Serialize};
+use sources::{
+    ActivitySource, CachedSource, FixtureSource, GhCliSource, GitHubApiClient, GitLabSource,
+    LocalGitSource, LocalGrouping, MultiHostSource, UnavailableSource, resolve_token,
+};
+use stats::{LabelStats, SizeStats, label_stats, size_stats};
 use std::fs;
-use std::path::PathBuf;
-use std::process::Command;
-
-fn find_gh_cli() -> Option<PathBuf> {
-    let common_paths = [
-        "/opt/homebrew/bin/gh",
-        "/usr/local/bin/gh",
-        "/usr/bin/gh",
-        "/opt/local/bin/gh",
-    ];
-
-    for path in common_paths {
-        let path_buf = PathBuf::from(path);
-        if path_buf.exists() {
-            return Some(path_buf);
-        }
-    }
-
-    if let Ok(output) = Command::new("/usr/bin/which").arg("gh").output() {
-        let path = String::from_utf8_lossy(&output.stdout).trim().to_string();
-        if output.status.success() && !path.is_empty() {
-            return Some(PathBuf::from(path));
-        }
-    }
-
-    None
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use structured::{STRUCTURED_MAX_TOKENS, StructuredSummary, SummaryFormat};
+use tauri::{AppHandle, Emitter, Manager, State};
+use templates::PromptTemplate;
+use tokio::process::Command;
+
+fn find_gh_cli() -> Result<PathBuf, AppError> {
+    gh_path::locate(read_settings().gh_path.as_deref())
 }
 
-fn gh_command() -> Result<Command, String> {
-    match find_gh_cli() {
-        Some(path) => Ok(Command::new(path)),
-        None => {
-            Err("GitHub CLI not found. Please install it from https://cli.github.com".to_string())
-        }
-    }
+fn gh_command() -> Result<Command, AppError> {
+    find_gh_cli().map(Command::new)
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -48,6 +59,34 @@ pub struct PullRequest {
     pub created_at: Option<String>,
     pub number: Option<i32>,
     pub repository: Repository,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<PullRequestSize>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub milestone: Option<String>,
+    #[serde(
+        default,
+        rename = "closingIssues",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub closing_issues: Vec<IssueReference>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct IssueReference {
+    pub number: i32,
+    pub title: String,
+    pub url: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+pub struct PullRequestSize {
+    pub additions: u32,
+    pub deletions: u32,
+    #[serde(rename = "changedFiles")]
+    pub changed_files: u32,
+    pub commits: u32,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -60,6 +99,95 @@ pub struct ReviewedPullRequest {
     pub created_at: String,
     pub author: Author,
     pub repository: Repository,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub review: Option<ReviewDetails>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewState {
+    Approved,
+    ChangesRequested,
+    Commented,
+    Dismissed,
+}
+
+impl ReviewState {
+    fn describe(self) -> &'static str {
+        match self {
+            ReviewState::Approved => "approved",
+            ReviewState::ChangesRequested => "requested changes",
+            ReviewState::Commented => "commented",
+            ReviewState::Dismissed => "dismissed",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct ReviewDetails {
+    #[serde(rename = "commentCount")]
+    pub comment_count: u32,
+    pub state: Option<ReviewState>,
+    #[serde(rename = "requestedAt")]
+    pub requested_at: Option<String>,
+    #[serde(rename = "firstReviewedAt")]
+    pub first_reviewed_at: Option<String>,
+    #[serde(rename = "minutesToFirstReview")]
+    pub minutes_to_first_review: Option<i64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum IssueInvolvement {
+    #[default]
+    Opened,
+    Closed,
+    Commented,
+}
+
+impl IssueInvolvement {
+    fn describe(self) -> &'static str {
+        match self {
+            IssueInvolvement::Opened => "opened",
+            IssueInvolvement::Closed => "closed as completed",
+            IssueInvolvement::Commented => "commented",
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Issue {
+    pub title: String,
+    pub url: String,
+    pub body: Option<String>,
+    pub state: String,
+    #[serde(rename = "createdAt")]
+    pub created_at: String,
+    #[serde(rename = "closedAt")]
+    pub closed_at: Option<String>,
+    pub number: Option<i32>,
+    pub repository: Repository,
+    #[serde(default)]
+    pub involvement: IssueInvolvement,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct IssuesResult {
+    pub success: bool,
+    pub data: Option<Vec<Issue>>,
+    pub complete: bool,
+    pub error: Option<AppError>,
+}
+
+impl IssuesResult {
+    fn failed(error: AppError) -> Self {
+        Self {
+            success: false,
+            data: None,
+            complete: false,
+            error: Some(error),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -71,7 +199,24 @@ pub struct Author {
 pub struct ReviewedResult {
     pub success: bool,
     pub data: Option<Vec<ReviewedPullRequest>>,
-    pub error: Option<String>,
+    pub complete: bool,
+    pub stale: bool,
+    #[serde(rename = "asOf")]
+    pub as_of: Option<String>,
+    pub error: Option<AppError>,
+}
+
+impl ReviewedResult {
+    fn failed(error: AppError) -> Self {
+        Self {
+            success: false,
+            data: None,
+            complete: false,
+            stale: false,
+            as_of: None,
+            error: Some(error),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -79,25 +224,137 @@ pub struct Repository {
     pub name: String,
     #[serde(rename = "nameWithOwner")]
     pub name_with_owner: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub host: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FetchResult {
     pub success: bool,
     pub data: Option<Vec<PullRequest>>,
-    pub error: Option<String>,
+    pub complete: bool,
+    pub stale: bool,
+    #[serde(rename = "asOf")]
+    pub as_of: Option<String>,
+    #[serde(rename = "sizeStats")]
+    pub size_stats: Option<SizeStats>,
+    #[serde(rename = "labelStats")]
+    pub label_stats: Option<LabelStats>,
+    pub error: Option<AppError>,
+}
+
+impl FetchResult {
+    fn failed(error: AppError) -> Self {
+        Self {
+            success: false,
+            data: None,
+            complete: false,
+            stale: false,
+            as_of: None,
+            size_stats: None,
+            label_stats: None,
+            error: Some(error),
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct AiResult {
     pub success: bool,
     pub summary: Option<String>,
-    pub error: Option<String>,
+    #[serde(default)]
+    pub stopped: bool,
+    #[serde(default)]
+    pub rubric: Option<Vec<CompetencyEvidence>>,
+    #[serde(default)]
+    pub structured: Option<StructuredSummary>,
+    pub error: Option<AppError>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl AiResult {
+    fn failed(error: AppError) -> Self {
+        Self {
+            success: false,
+            summary: None,
+            stopped: false,
+            rubric: None,
+            structured: None,
+            error: Some(error),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DataSource {
+    #[default]
+    GhCli,
+    GithubApi,
+    Fixture,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AiProvider {
+    #[default]
+    Openai,
+    Anthropic,
+    AzureOpenai,
+    OpenaiCompatible,
+    Ollama,
+}
+
+impl AiProvider {
+    pub fn requires_api_key(self) -> bool {
+        !matches!(self, AiProvider::OpenaiCompatible | AiProvider::Ollama)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GitHubHost {
+    pub host: String,
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GitLabInstance {
+    pub url: String,
+    pub token: String,
+}
+
+impl GitLabInstance {
+    fn host(&self) -> String {
+        reqwest::Url::parse(&self.url)
+            .ok()
+            .and_then(|url| url.host_str().map(|h| h.to_string()))
+            .unwrap_or_else(|| self.url.clone())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Default)]
 pub struct AppSettings {
     pub api_key: Option<String>,
+    pub github_token: Option<String>,
+    #[serde(default)]
+    pub github_hosts: Vec<GitHubHost>,
+    #[serde(default)]
+    pub gitlab_instances: Vec<GitLabInstance>,
+    #[serde(default)]
+    pub local_repositories: Vec<String>,
+    #[serde(default)]
+    pub author_emails: Vec<String>,
+    #[serde(default)]
+    pub data_source: DataSource,
+    pub fixture_dir: Option<String>,
+    #[serde(default)]
+    pub gh_path: Option<String>,
+    #[serde(default)]
+    pub ai_provider: AiProvider,
+    pub ai_model: Option<String>,
+    pub ai_base_url: Option<String>,
+    pub azure_api_version: Option<String>,
+    pub rubric_path: Option<String>,
+    pub rubric_level: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -115,68 +372,59 @@ pub struct SavedReport {
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SaveReportResult {
     pub success: bool,
-    pub error: Option<String>,
+    pub error: Option<AppError>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoadReportsResult {
     pub success: bool,
     pub reports: Option<Vec<SavedReport>>,
-    pub error: Option<String>,
+    pub error: Option<AppError>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct LoadSettingsResult {
     pub success: bool,
     pub settings: Option<AppSettings>,
-    pub error: Option<String>,
+    pub error: Option<AppError>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct OrganizationsResult {
     pub success: bool,
     pub organizations: Option<Vec<String>>,
-    pub error: Option<String>,
-}
-
-#[derive(Debug, Deserialize)]
-struct SimplePr {
-    repository: SimpleRepo,
-}
-
-#[derive(Debug, Deserialize)]
-struct SimpleRepo {
-    #[serde(rename = "nameWithOwner")]
-    name_with_owner: String,
-}
-
-#[derive(Debug, Serialize)]
-struct OpenAiRequest {
-    model: String,
-    messages: Vec<OpenAiMessage>,
-    max_tokens: u32,
-    temperature: f32,
-}
-
-#[derive(Debug, Serialize)]
-struct OpenAiMessage {
-    role: String,
-    content: String,
+    pub complete: bool,
+    pub stale: bool,
+    #[serde(rename = "asOf")]
+    pub as_of: Option<String>,
+    pub error: Option<AppError>,
 }
 
-#[derive(Debug, Deserialize)]
-struct OpenAiResponse {
-    choices: Vec<OpenAiChoice>,
+impl OrganizationsResult {
+    fn failed(error: AppError) -> Self {
+        Self {
+            success: false,
+            organizations: None,
+            complete: false,
+            stale: false,
+            as_of: None,
+            error: Some(error),
+        }
+    }
 }
 
-#[derive(Debug, Deserialize)]
-struct OpenAiChoice {
-    message: OpenAiMessageResponse,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct PromptTemplatesResult {
+    pub success: bool,
+    pub templates: Option<Vec<PromptTemplate>>,
+    pub error: Option<AppError>,
 }
 
-#[derive(Debug, Deserialize)]
-struct OpenAiMessageResponse {
-    content: String,
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ModelsResult {
+    pub success: bool,
+    pub models: Option<Vec<String>>,
+    pub error: Option<AppError>,
 }
 
 fn get_data_dir() -> PathBuf {
@@ -190,6 +438,10 @@ fn get_data_dir() -> PathBuf {
     data_dir
 }
 
+fn get_cache_path() -> PathBuf {
+    get_data_dir().join("cache.sqlite3")
+}
+
 fn get_settings_path() -> PathBuf {
     get_data_dir().join("settings.json")
 }
@@ -198,92 +450,249 @@ fn get_reports_path() -> PathBuf {
     get_data_dir().join("reports.json")
 }
 
-#[tauri::command]
-fn fetch_organizations(start_date: String, end_date: String) -> OrganizationsResult {
-    let date_range = format!("{}..{}", start_date, end_date);
+fn get_templates_dir() -> PathBuf {
+    get_data_dir().join("templates")
+}
 
-    let mut cmd = match gh_command() {
-        Ok(cmd) => cmd,
-        Err(e) => {
-            return OrganizationsResult {
-                success: false,
-                organizations: None,
-                error: Some(e),
-            };
+fn read_settings() -> AppSettings {
+    fs::read_to_string(get_settings_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+impl AppSettings {
+    fn github_hosts(&self) -> Vec<GitHubHost> {
+        if self.github_hosts.is_empty() {
+            vec![GitHubHost {
+                host: "github.com".to_string(),
+                token: self.github_token.clone(),
+            }]
+        } else {
+            self.github_hosts.clone()
         }
-    };
+    }
+}
 
-    let output = cmd
-        .args([
-            "search",
-            "prs",
-            "--author",
-            "@me",
-            "--merged-at",
-            &date_range,
-            "--json",
-            "repository",
-            "--limit",
-            "100",
-        ])
-        .output();
-
-    match output {
-        Ok(result) => {
-            if result.status.success() {
-                let stdout = String::from_utf8_lossy(&result.stdout);
-
-                match serde_json::from_str::<Vec<SimplePr>>(&stdout) {
-                    Ok(prs) => {
-                        let mut orgs: Vec<String> = prs
-                            .iter()
-                            .filter_map(|pr| {
-                                pr.repository
-                                    .name_with_owner
-                                    .split('/')
-                                    .next()
-                                    .map(|s| s.to_string())
-                            })
-                            .collect();
-
-                        orgs.sort();
-                        orgs.dedup();
-
-                        OrganizationsResult {
-                            success: true,
-                            organizations: Some(orgs),
-                            error: None,
-                        }
-                    }
-                    Err(e) => OrganizationsResult {
-                        success: false,
-                        organizations: None,
-                        error: Some(format!("Failed to parse GitHub response: {}", e)),
+fn activity_source(settings: &AppSettings) -> Result<Box<dyn ActivitySource>, AppError> {
+    match settings.data_source {
+        DataSource::GhCli | DataSource::GithubApi => {
+            let mut sources: Vec<(String, Box<dyn ActivitySource>)> = Vec::new();
+
+            for h in settings.github_hosts() {
+                let source: Box<dyn ActivitySource> = match settings.data_source {
+                    DataSource::GithubApi => match resolve_token(h.token.as_deref(), &h.host) {
+                        Ok(token) => Box::new(GitHubApiClient::new(token, &h.host)),
+                        Err(e) => Box::new(UnavailableSource::new(e)),
                     },
-                }
-            } else {
-                let stderr = String::from_utf8_lossy(&result.stderr);
-                OrganizationsResult {
-                    success: false,
-                    organizations: None,
-                    error: Some(format!("GitHub CLI error: {}", stderr)),
-                }
+                    _ => Box::new(GhCliSource::new(&h.host)),
+                };
+                sources.push((h.host, source));
+            }
+
+            for instance in &settings.gitlab_instances {
+                let source = GitLabSource::new(&instance.url, instance.token.clone());
+                sources.push((instance.host(), Box::new(source)));
+            }
+
+            if let Ok(cache) = ActivityCache::open(&get_cache_path()) {
+                let cache = Arc::new(cache);
+                sources = sources
+                    .into_iter()
+                    .map(|(host, source)| {
+                        let cached = CachedSource::new(source, cache.clone(), &host);
+                        (host, Box::new(cached) as Box<dyn ActivitySource>)
+                    })
+                    .collect();
+            }
+
+            Ok(Box::new(MultiHostSource::new(sources)))
+        }
+        DataSource::Fixture => settings
+            .fixture_dir
+            .as_ref()
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| Box::new(FixtureSource::new(dir)) as Box<dyn ActivitySource>)
+            .ok_or_else(|| {
+                AppError::new(
+                    ErrorCode::Config,
+                    "The fixture data source requires `fixture_dir` in settings",
+                )
+            }),
+    }
+}
+
+fn llm_provider(settings: &AppSettings, api_key: &str) -> Result<Box<dyn LlmProvider>, AppError> {
+    let model = settings.ai_model.as_deref().filter(|m| !m.is_empty());
+    let base_url = settings.ai_base_url.as_deref().filter(|u| !u.is_empty());
+    let missing = |setting: &str| {
+        AppError::new(
+            ErrorCode::Config,
+            format!(
+                "The {} AI provider requires `{}` in settings",
+                serde_json::to_string(&settings.ai_provider).unwrap_or_default(),
+                setting
+            ),
+        )
+    };
+
+    if settings.ai_provider.requires_api_key() && api_key.is_empty() {
+        return Err(AppError::new(ErrorCode::Config, "AI API key is required"));
+    }
+
+    Ok(match settings.ai_provider {
+        AiProvider::Openai => Box::new(OpenAiProvider::openai(api_key, model)),
+        AiProvider::Anthropic => Box::new(AnthropicProvider::new(api_key, model)),
+        AiProvider::AzureOpenai => Box::new(OpenAiProvider::azure(
+            base_url.ok_or_else(|| missing("ai_base_url"))?,
+            model.ok_or_else(|| missing("ai_model"))?,
+            settings
+                .azure_api_version
+                .as_deref()
+                .unwrap_or(llm::DEFAULT_AZURE_API_VERSION),
+            api_key,
+        )),
+        AiProvider::OpenaiCompatible => Box::new(OpenAiProvider::compatible(
+            base_url.ok_or_else(|| missing("ai_base_url"))?,
+            model.ok_or_else(|| missing("ai_model"))?,
+            Some(api_key),
+        )),
+        AiProvider::Ollama => Box::new(OllamaProvider::new(
+            base_url.unwrap_or(llm::DEFAULT_OLLAMA_URL),
+            model.ok_or_else(|| missing("ai_model"))?,
+        )),
+    })
+}
+
+fn organizations_from(
+    source: &dyn ActivitySource,
+    start_date: &str,
+    end_date: &str,
+) -> OrganizationsResult {
+    match source.search_owners(start_date, end_date) {
+        Ok(outcome) => {
+            let mut orgs = outcome.items;
+            orgs.retain(|org| !org.is_empty());
+            orgs.sort();
+            orgs.dedup();
+            let as_of = source.as_of();
+
+            OrganizationsResult {
+                success: true,
+                organizations: Some(orgs),
+                complete: outcome.complete,
+                stale: as_of.is_some(),
+                as_of,
+                error: None,
+            }
+        }
+        Err(e) => OrganizationsResult::failed(e),
+    }
+}
+
+fn activity_from(
+    source: &dyn ActivitySource,
+    start_date: &str,
+    end_date: &str,
+    org_name: Option<&str>,
+) -> FetchResult {
+    let owner = org_name.filter(|o| !o.is_empty());
+
+    match source.search_authored(start_date, end_date, owner) {
+        Ok(outcome) => {
+            let as_of = source.as_of();
+            FetchResult {
+                success: true,
+                size_stats: Some(size_stats(&outcome.items)),
+                label_stats: Some(label_stats(&outcome.items)),
+                data: Some(outcome.items),
+                complete: outcome.complete,
+                stale: as_of.is_some(),
+                as_of,
+                error: None,
+            }
+        }
+        Err(e) => FetchResult::failed(e),
+    }
+}
+
+fn reviewed_from(source: &dyn ActivitySource, start_date: &str, end_date: &str) -> ReviewedResult {
+    match source.search_reviewed(start_date, end_date) {
+        Ok(outcome) => {
+            let as_of = source.as_of();
+            ReviewedResult {
+                success: true,
+                data: Some(outcome.items),
+                complete: outcome.complete,
+                stale: as_of.is_some(),
+                as_of,
+                error: None,
+            }
+        }
+        Err(e) => ReviewedResult::failed(e),
+    }
+}
+
+fn issues_from(
+    source: &dyn ActivitySource,
+    start_date: &str,
+    end_date: &str,
+    org_name: Option<&str>,
+) -> IssuesResult {
+    let owner = org_name.filter(|o| !o.is_empty());
+    let mut issues: Vec<Issue> = Vec::new();
+    let mut complete = true;
+
+    for involvement in [
+        IssueInvolvement::Closed,
+        IssueInvolvement::Opened,
+        IssueInvolvement::Commented,
+    ] {
+        let outcome = match source.search_issues(start_date, end_date, owner, involvement) {
+            Ok(outcome) => outcome,
+            Err(e) => return IssuesResult::failed(e),
+        };
+
+        complete &= outcome.complete;
+        for mut issue in outcome.items {
+            if issues.iter().all(|existing| existing.url != issue.url) {
+                issue.involvement = involvement;
+                issues.push(issue);
             }
         }
-        Err(e) => OrganizationsResult {
-            success: false,
-            organizations: None,
-            error: Some(format!("Failed to execute GitHub CLI: {}", e)),
-        },
+    }
+
+    IssuesResult {
+        success: true,
+        data: Some(issues),
+        complete,
+        error: None,
     }
 }
 
 #[tauri::command]
-fn save_settings(api_key: Option<String>) -> SaveReportResult {
-    let settings = AppSettings { api_key };
+async fn fetch_organizations(
+    app: AppHandle,
+    start_date: String,
+    end_date: String,
+    request_id: Option<String>,
+) -> OrganizationsResult {
+    app.state::<InFlight>()
+        .run(
+            request_id,
+            move || match activity_source(&read_settings()) {
+                Ok(source) => organizations_from(source.as_ref(), &start_date, &end_date),
+                Err(e) => OrganizationsResult::failed(e),
+            },
+        )
+        .await
+        .unwrap_or_else(OrganizationsResult::failed)
+}
+
+fn write_settings(settings: &AppSettings) -> SaveReportResult {
     let path = get_settings_path();
 
-    match serde_json::to_string_pretty(&settings) {
+    match serde_json::to_string_pretty(settings) {
         Ok(json) => match fs::write(&path, json) {
             Ok(_) => SaveReportResult {
                 success: true,
@@ -291,16 +700,35 @@ fn save_settings(api_key: Option<String>) -> SaveReportResult {
             },
             Err(e) => SaveReportResult {
                 success: false,
-                error: Some(format!("Failed to save settings: {}", e)),
+                error: Some(AppError::new(
+                    ErrorCode::Io,
+                    format!("Failed to save settings: {}", e),
+                )),
             },
         },
         Err(e) => SaveReportResult {
             success: false,
-            error: Some(format!("Failed to serialize settings: {}", e)),
+            error: Some(AppError::new(
+                ErrorCode::Parse,
+                format!("Failed to serialize settings: {}", e),
+            )),
         },
     }
 }
 
+#[tauri::command]
+fn save_settings(api_key: Option<String>) -> SaveReportResult {
+    write_settings(&AppSettings {
+        api_key,
+        ..read_settings()
+    })
+}
+
+#[tauri::command]
+fn update_settings(settings: AppSettings) -> SaveReportResult {
+    write_settings(&settings)
+}
+
 #[tauri::command]
 fn load_settings() -> LoadSettingsResult {
     let path = get_settings_path();
@@ -308,7 +736,7 @@ fn load_settings() -> LoadSettingsResult {
     if !path.exists() {
         return LoadSettingsResult {
             success: true,
-            settings: Some(AppSettings { api_key: None }),
+            settings: Some(AppSettings::default()),
             error: None,
         };
     }
@@ -323,13 +751,19 @@ fn load_settings() -> LoadSettingsResult {
             Err(e) => LoadSettingsResult {
                 success: false,
                 settings: None,
-                error: Some(format!("Failed to parse settings: {}", e)),
+                error: Some(AppError::new(
+                    ErrorCode::Parse,
+                    format!("Failed to parse settings: {}", e),
+                )),
             },
         },
         Err(e) => LoadSettingsResult {
             success: false,
             settings: None,
-            error: Some(format!("Failed to read settings: {}", e)),
+            error: Some(AppError::new(
+                ErrorCode::Io,
+                format!("Failed to read settings: {}", e),
+            )),
         },
     }
 }
@@ -361,12 +795,18 @@ fn save_report(report: SavedReport) -> SaveReportResult {
             },
             Err(e) => SaveReportResult {
                 success: false,
-                error: Some(format!("Failed to save report: {}", e)),
+                error: Some(AppError::new(
+                    ErrorCode::Io,
+                    format!("Failed to save report: {}", e),
+                )),
             },
         },
         Err(e) => SaveReportResult {
             success: false,
-            error: Some(format!("Failed to serialize reports: {}", e)),
+            error: Some(AppError::new(
+                ErrorCode::Parse,
+                format!("Failed to serialize reports: {}", e),
+            )),
         },
     }
 }
@@ -393,13 +833,19 @@ fn load_reports() -> LoadReportsResult {
             Err(e) => LoadReportsResult {
                 success: false,
                 reports: None,
-                error: Some(format!("Failed to parse reports: {}", e)),
+                error: Some(AppError::new(
+                    ErrorCode::Parse,
+                    format!("Failed to parse reports: {}", e),
+                )),
             },
         },
         Err(e) => LoadReportsResult {
             success: false,
             reports: None,
-            error: Some(format!("Failed to read reports: {}", e)),
+            error: Some(AppError::new(
+                ErrorCode::Io,
+                format!("Failed to read reports: {}", e),
+            )),
         },
     }
 }
@@ -430,176 +876,305 @@ fn delete_report(report_id: String) -> SaveReportResult {
             },
             Err(e) => SaveReportResult {
                 success: false,
-                error: Some(format!("Failed to save reports: {}", e)),
+                error: Some(AppError::new(
+                    ErrorCode::Io,
+                    format!("Failed to save reports: {}", e),
+                )),
             },
         },
         Err(e) => SaveReportResult {
             success: false,
-            error: Some(format!("Failed to serialize reports: {}", e)),
+            error: Some(AppError::new(
+                ErrorCode::Parse,
+                format!("Failed to serialize reports: {}", e),
+            )),
+        },
+    }
+}
+
+#[tauri::command]
+fn clear_activity_cache() -> SaveReportResult {
+    let path = get_cache_path();
+
+    if !path.exists() {
+        return SaveReportResult {
+            success: true,
+            error: None,
+        };
+    }
+
+    match ActivityCache::open(&path).and_then(|cache| cache.clear()) {
+        Ok(_) => SaveReportResult {
+            success: true,
+            error: None,
+        },
+        Err(e) => SaveReportResult {
+            success: false,
+            error: Some(e),
         },
     }
 }
 
+async fn run_fetch<T, F>(
+    app: &AppHandle,
+    kind: &'static str,
+    request_id: Option<String>,
+    work: F,
+) -> Result<T, AppError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> T + Send + 'static,
+{
+    let emitter = app.clone();
+    let progress_id = request_id.clone();
+
+    app.state::<InFlight>()
+        .run(request_id, move || {
+            let emit = move |progress: &progress::FetchProgress| {
+                let _ = emitter.emit(FETCH_PROGRESS_EVENT, progress.clone());
+            };
+            progress::track(kind, progress_id, emit, work)
+        })
+        .await
+}
+
 #[tauri::command]
-fn fetch_github_activity(
+async fn fetch_github_activity(
+    app: AppHandle,
     start_date: String,
     end_date: String,
     org_name: Option<String>,
+    request_id: Option<String>,
 ) -> FetchResult {
-    let date_range = format!("{}..{}", start_date, end_date);
-
-    let mut args = vec![
-        "search".to_string(),
-        "prs".to_string(),
-        "--author".to_string(),
-        "@me".to_string(),
-        "--merged-at".to_string(),
-        date_range,
-        "--json".to_string(),
-        "title,url,body,closedAt,createdAt,number,repository".to_string(),
-        "--limit".to_string(),
-        "200".to_string(),
-    ];
-
-    if let Some(org) = org_name.as_ref().filter(|o| !o.is_empty()) {
-        args.insert(4, "--owner".to_string());
-        args.insert(5, org.to_string());
-    }
-
-    let mut cmd = match gh_command() {
-        Ok(cmd) => cmd,
-        Err(e) => {
-            return FetchResult {
-                success: false,
-                data: None,
-                error: Some(e),
-            };
-        }
-    };
-
-    let output = cmd.args(&args).output();
+    run_fetch(
+        &app,
+        "authored",
+        request_id,
+        move || match activity_source(&read_settings()) {
+            Ok(source) => {
+                activity_from(source.as_ref(), &start_date, &end_date, org_name.as_deref())
+            }
+            Err(e) => FetchResult::failed(e),
+        },
+    )
+    .await
+    .unwrap_or_else(FetchResult::failed)
+}
 
-    match output {
-        Ok(result) => {
-            if result.status.success() {
-                let stdout = String::from_utf8_lossy(&result.stdout);
+#[tauri::command]
+async fn fetch_reviewed_prs(
+    app: AppHandle,
+    start_date: String,
+    end_date: String,
+    request_id: Option<String>,
+) -> ReviewedResult {
+    run_fetch(
+        &app,
+        "reviewed",
+        request_id,
+        move || match activity_source(&read_settings()) {
+            Ok(source) => reviewed_from(source.as_ref(), &start_date, &end_date),
+            Err(e) => ReviewedResult::failed(e),
+        },
+    )
+    .await
+    .unwrap_or_else(ReviewedResult::failed)
+}
 
-                match serde_json::from_str::<Vec<PullRequest>>(&stdout) {
-                    Ok(prs) => FetchResult {
-                        success: true,
-                        data: Some(prs),
-                        error: None,
-                    },
-                    Err(e) => FetchResult {
-                        success: false,
-                        data: None,
-                        error: Some(format!("Failed to parse GitHub response: {}", e)),
-                    },
-                }
-            } else {
-                let stderr = String::from_utf8_lossy(&result.stderr);
-                FetchResult {
-                    success: false,
-                    data: None,
-                    error: Some(format!("GitHub CLI error: {}", stderr)),
+#[tauri::command]
+async fn fetch_issues(
+    app: AppHandle,
+    start_date: String,
+    end_date: String,
+    org_name: Option<String>,
+    request_id: Option<String>,
+) -> IssuesResult {
+    app.state::<InFlight>()
+        .run(
+            request_id,
+            move || match activity_source(&read_settings()) {
+                Ok(source) => {
+                    issues_from(source.as_ref(), &start_date, &end_date, org_name.as_deref())
                 }
-            }
-        }
-        Err(e) => FetchResult {
-            success: false,
-            data: None,
-            error: Some(format!("Failed to execute GitHub CLI: {}", e)),
-        },
+                Err(e) => IssuesResult::failed(e),
+            },
+        )
+        .await
+        .unwrap_or_else(IssuesResult::failed)
+}
+
+#[tauri::command]
+async fn fetch_local_activity(
+    app: AppHandle,
+    start_date: String,
+    end_date: String,
+    group_by: Option<LocalGrouping>,
+    request_id: Option<String>,
+) -> FetchResult {
+    let settings = read_settings();
+
+    if settings.local_repositories.is_empty() || settings.author_emails.is_empty() {
+        return FetchResult::failed(AppError::new(
+            ErrorCode::Config,
+            "Configure `local_repositories` and `author_emails` in settings to scan local repositories",
+        ));
     }
+
+    app.state::<InFlight>()
+        .run(request_id, move || {
+            let source = LocalGitSource::new(
+                &settings.local_repositories,
+                &settings.author_emails,
+                group_by.unwrap_or_default(),
+            );
+            activity_from(&source, &start_date, &end_date, None)
+        })
+        .await
+        .unwrap_or_else(FetchResult::failed)
 }
 
 #[tauri::command]
-fn fetch_reviewed_prs(start_date: String, end_date: String) -> ReviewedResult {
-    let date_range = format!("{}..{}", start_date, end_date);
+fn cancel_fetch(in_flight: State<'_, InFlight>, request_id: String) -> bool {
+    in_flight.cancel(&request_id)
+}
 
-    let mut cmd = match gh_command() {
-        Ok(cmd) => cmd,
-        Err(e) => {
-            return ReviewedResult {
-                success: false,
-                data: None,
-                error: Some(e),
-            };
-        }
-    };
+fn pr_context(pr: &PullRequest) -> String {
+    let mut context = String::new();
+
+    if let Some(size) = pr.size {
+        context.push_str(&format!(
+            "\n  Size: +{}/-{} lines across {} files in {} commits ({})",
+            size.additions,
+            size.deletions,
+            size.changed_files,
+            size.commits,
+            size.category().describe()
+        ));
+    }
+    if !pr.labels.is_empty() {
+        context.push_str(&format!("\n  Labels: {}", pr.labels.join(", ")));
+    }
+    if let Some(milestone) = &pr.milestone {
+        context.push_str(&format!("\n  Milestone: {}", milestone));
+    }
+    if !pr.closing_issues.is_empty() {
+        let issues: Vec<String> = pr
+            .closing_issues
+            .iter()
+            .map(|issue| format!("#{} {}", issue.number, issue.title))
+            .collect();
+        context.push_str(&format!("\n  Closes: {}", issues.join("; ")));
+    }
 
-    let output = cmd
-        .args([
-            "search",
-            "prs",
-            "--reviewed-by",
-            "@me",
-            "--merged-at",
-            &date_range,
-            "--json",
-            "title,url,closedAt,createdAt,author,repository",
-            "--limit",
-            "200",
-        ])
-        .output();
-
-    match output {
-        Ok(result) => {
-            if result.status.success() {
-                let stdout = String::from_utf8_lossy(&result.stdout);
-
-                match serde_json::from_str::<Vec<ReviewedPullRequest>>(&stdout) {
-                    Ok(prs) => ReviewedResult {
-                        success: true,
-                        data: Some(prs),
-                        error: None,
-                    },
-                    Err(e) => ReviewedResult {
-                        success: false,
-                        data: None,
-                        error: Some(format!("Failed to parse GitHub response: {}", e)),
-                    },
+    context
+}
+
+fn review_section(reviewed_prs: &[ReviewedPullRequest]) -> String {
+    if reviewed_prs.is_empty() {
+        return String::new();
+    }
+
+    let review_list: Vec<String> = reviewed_prs
+        .iter()
+        .map(|pr| {
+            let mut line = format!(
+                "- **{}** ({}, by {})",
+                pr.title, pr.repository.name, pr.author.login
+            );
+            if let Some(review) = &pr.review {
+                if let Some(state) = review.state {
+                    line.push_str(&format!(": {}", state.describe()));
                 }
-            } else {
-                let stderr = String::from_utf8_lossy(&result.stderr);
-                ReviewedResult {
-                    success: false,
-                    data: None,
-                    error: Some(format!("GitHub CLI error: {}", stderr)),
+                line.push_str(&format!(", {} review comments", review.comment_count));
+                if let Some(minutes) = review.minutes_to_first_review {
+                    line.push_str(&format!(
+                        ", first review {:.1} hours after being requested",
+                        minutes as f64 / 60.0
+                    ));
                 }
             }
-        }
-        Err(e) => ReviewedResult {
-            success: false,
-            data: None,
-            error: Some(format!("Failed to execute GitHub CLI: {}", e)),
-        },
+            line
+        })
+        .collect();
+
+    format!(
+        "\n\nThe engineer also reviewed these {} merged pull requests from teammates. Use the review outcomes, comment counts and response times as evidence of review effort for Collaboration & Leadership:\n\n{}",
+        reviewed_prs.len(),
+        review_list.join("\n")
+    )
+}
+
+async fn summarize_part(
+    provider: &dyn LlmProvider,
+    prompt: &str,
+    token: &CancelToken,
+    mut on_tokens: impl FnMut(u32),
+) -> Result<String, AppError> {
+    let generation = llm::generate(provider, prompt, CHUNK_MAX_TOKENS, token, |_, tokens| {
+        on_tokens(tokens)
+    })
+    .await?;
+
+    if generation.stopped {
+        return Err(AppError::cancelled());
     }
+    Ok(generation.text.trim().to_string())
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 async fn generate_ai_summary(
+    app: AppHandle,
     api_key: String,
     prs: Vec<PullRequest>,
     date_range: String,
     org_name: String,
+    issues: Option<Vec<Issue>>,
+    reviewed_prs: Option<Vec<ReviewedPullRequest>>,
+    chunking: Option<ChunkStrategy>,
+    template: Option<String>,
+    rubric_path: Option<String>,
+    level: Option<String>,
+    language: Option<Language>,
+    format: Option<SummaryFormat>,
+    request_id: Option<String>,
 ) -> AiResult {
-    if api_key.is_empty() {
-        return AiResult {
-            success: false,
-            summary: None,
-            error: Some("OpenAI API key is required".to_string()),
-        };
-    }
+    let settings = read_settings();
+    let provider = match llm_provider(&settings, &api_key) {
+        Ok(provider) => provider,
+        Err(e) => return AiResult::failed(e),
+    };
 
-    if prs.is_empty() {
-        return AiResult {
-            success: false,
-            summary: None,
-            error: Some("No pull requests to summarize".to_string()),
-        };
+    let level = level.or(settings.rubric_level).filter(|l| !l.is_empty());
+    let rubric = match rubric_path
+        .or(settings.rubric_path)
+        .filter(|p| !p.is_empty())
+    {
+        Some(path) => match rubric::load(Path::new(&path)) {
+            Ok(rubric) => Some(rubric),
+            Err(e) => return AiResult::failed(e),
+        },
+        None => None,
+    };
+
+    let language = language.unwrap_or_default();
+    let format = format.unwrap_or_default();
+    let issues = issues.unwrap_or_default();
+
+    if prs.is_empty() && issues.is_empty() {
+        return AiResult::failed(AppError::new(
+            ErrorCode::InvalidInput,
+            "No pull requests to summarize",
+        ));
     }
 
+    let template = match templates::load(&get_templates_dir(), template.as_deref())
+        .and_then(|template| templates::validate(&template).map(|_| template))
+    {
+        Ok(template) => template,
+        Err(e) => return AiResult::failed(e),
+    };
+
     let pr_summaries: Vec<String> = prs
         .iter()
         .map(|pr| {
@@ -614,113 +1189,378 @@ async fn generate_ai_summary(
                     }
                 })
                 .unwrap_or_else(|| "No description".to_string());
+            let url = if format == SummaryFormat::Json {
+                format!("\n  URL: {}", pr.url)
+            } else {
+                String::new()
+            };
             format!(
-                "- **{}** ({})\n  {}\n  Merged: {}",
-                pr.title, pr.repository.name, body_preview, pr.closed_at
+                "- **{}** ({})\n  {}\n  Merged: {}{}{}",
+                pr.title,
+                pr.repository.name,
+                body_preview,
+                pr.closed_at,
+                url,
+                pr_context(pr)
             )
         })
         .collect();
 
-    let pr_list = pr_summaries.join("\n\n");
-
-    let prompt = format!(
-        r#"You are an expert at writing performance review self-assessments for software engineers.
-
-CRITICAL RULES:
-- NEVER invent or fabricate metrics, percentages, or statistics (like "15% improvement" or "reduced load time by 30%")
-- NEVER claim outcomes you cannot verify from the PR data (like "increased user engagement" or "improved customer satisfaction")
-- Only describe what the PRs actually show was built or changed
-- Focus on the WORK DONE, not imagined business outcomes
-- If you don't know the impact, describe the technical contribution without making up numbers
-
-Based on the following merged pull requests from {} at {}, write a performance review summary that:
+    let issue_section = if issues.is_empty() {
+        String::new()
+    } else {
+        let issue_list: Vec<String> = issues
+            .iter()
+            .map(|issue| {
+                format!(
+                    "- **{}** ({}, {})",
+                    issue.title,
+                    issue.repository.name,
+                    issue.involvement.describe()
+                )
+            })
+            .collect();
+
+        format!(
+            "\n\nThe engineer was also involved in these {} issues (bug reports, triage, planning and design discussions). Use them as supporting evidence, especially for Collaboration & Leadership:\n\n{}",
+            issues.len(),
+            issue_list.join("\n")
+        )
+    };
 
-1. **Impact Summary** (2-3 sentences): High-level overview of what was built/improved. Describe the scope and nature of contributions without fabricating metrics.
+    let reviewed_prs = reviewed_prs.unwrap_or_default();
+    let review_section = review_section(&reviewed_prs);
+    let rubric_section = rubric
+        .as_ref()
+        .map(|r| rubric::prompt_section(r, level.as_deref()))
+        .unwrap_or_default();
 
-2. **Key Achievements** (3-5 bullet points): Specific accomplishments based ONLY on what the PRs show. Mention the actual features, fixes, or improvements made. Do NOT add fake statistics.
+    let strategy = chunking.unwrap_or_default();
+    let reserved = [&issue_section, &review_section, &rubric_section]
+        .iter()
+        .map(|section| chunking::estimate_tokens(section))
+        .sum();
+    let chunks = chunking::plan(
+        &prs,
+        &pr_summaries,
+        strategy,
+        provider.prompt_token_budget(),
+        reserved,
+    );
+    let chunk_count = if chunks.len() > 1 { chunks.len() } else { 0 };
+    let token = app.state::<InFlight>().register(request_id.as_deref());
+
+    let emit_ai_progress = |phase: AiPhase, tokens: u32, chunks_done: usize, chunks: usize| {
+        let _ = app.emit(
+            AI_PROGRESS_EVENT,
+            AiProgress {
+                request_id: request_id.clone(),
+                phase,
+                tokens,
+                chunks_done,
+                chunks,
+            },
+        );
+    };
 
-3. **Technical Growth**: Areas of technical skill development demonstrated based on the types of work shown in the PRs.
+    let (pr_heading, pr_list) = if chunk_count == 0 {
+        (
+            format!("Here are the {} merged pull requests:", prs.len()),
+            pr_summaries.join("\n\n"),
+        )
+    } else {
+        let mut parts = chunk_count;
+        let mut notes = Vec::new();
+        for (done, chunk) in chunks.iter().enumerate() {
+            emit_ai_progress(AiPhase::Summarizing, 0, done, parts);
+            let prompt = chunking::chunk_prompt(chunk, &date_range, &org_name);
+            match summarize_part(provider.as_ref(), &prompt, &token, |tokens| {
+                emit_ai_progress(AiPhase::Summarizing, tokens, done, parts)
+            })
+            .await
+            {
+                Ok(text) => notes.push((chunk.label.clone(), text)),
+                Err(e) => return AiResult::failed(e),
+            }
+        }
 
-4. **Collaboration & Leadership**: Only mention if clearly evidenced in PR descriptions (e.g., mentions of reviews, pair programming, helping others).
+        let mut done = parts;
+        while let Some(batches) =
+            chunking::reduce_plan(&notes, provider.prompt_token_budget(), reserved)
+        {
+            parts += batches.len();
+            let mut condensed = Vec::new();
+            for batch in &batches {
+                emit_ai_progress(AiPhase::Summarizing, 0, done, parts);
+                let prompt = chunking::reduce_prompt(batch, &date_range, &org_name);
+                match summarize_part(provider.as_ref(), &prompt, &token, |tokens| {
+                    emit_ai_progress(AiPhase::Summarizing, tokens, done, parts)
+                })
+                .await
+                {
+                    Ok(text) => condensed.push((batch.label.clone(), text)),
+                    Err(e) => return AiResult::failed(e),
+                }
+                done += 1;
+            }
+            notes = condensed;
+        }
 
-5. **Recommended Talking Points**: 2-3 specific PRs that seem significant based on their titles/descriptions. Explain why they might be good to discuss.
+        let notes: Vec<String> = notes
+            .iter()
+            .map(|(label, text)| chunking::format_note(label, text))
+            .collect();
+        (
+            format!(
+                "The {} merged pull requests are too many to list in full, so here are notes on them, grouped by {}:",
+                prs.len(),
+                strategy.describe()
+            ),
+            notes.join("\n\n"),
+        )
+    };
 
-Here are the {} merged pull requests:
+    let headings = language.headings();
+    let language_section = language
+        .prompt_section(templates::is_builtin_default(&template).then_some(headings.as_slice()));
+
+    let mut prompt = match templates::render(
+        &template,
+        &[
+            ("date_range", date_range.clone()),
+            ("org_name", org_name.clone()),
+            ("pr_count", prs.len().to_string()),
+            ("pr_heading", pr_heading),
+            ("pr_list", pr_list),
+            ("issue_count", issues.len().to_string()),
+            ("issue_section", issue_section),
+            ("review_count", reviewed_prs.len().to_string()),
+            ("review_section", review_section),
+            ("rubric_section", rubric_section),
+            ("language_section", language_section.clone()),
+        ],
+    ) {
+        Ok(prompt) => prompt,
+        Err(e) => return AiResult::failed(e),
+    };
+    if !templates::uses_placeholder(&template, "language_section") {
+        prompt.push_str(&language_section);
+    }
+    let max_tokens = match format {
+        SummaryFormat::Markdown => 2000,
+        SummaryFormat::Json => {
+            prompt.push_str(&structured::prompt_section(language));
+            STRUCTURED_MAX_TOKENS
+        }
+    };
 
-{}
+    emit_ai_progress(AiPhase::Generating, 0, 0, 0);
+
+    let generation = llm::generate(
+        provider.as_ref(),
+        &prompt,
+        max_tokens,
+        &token,
+        |delta, tokens| {
+            if format == SummaryFormat::Markdown {
+                let _ = app.emit(
+                    AI_CHUNK_EVENT,
+                    AiChunk {
+                        request_id: request_id.clone(),
+                        delta: delta.to_string(),
+                    },
+                );
+            }
+            emit_ai_progress(AiPhase::Generating, tokens, 0, 0);
+        },
+    )
+    .await;
 
-Write in first person. Be professional and confident, but STICK TO THE FACTS shown in the PRs. Describe what was built, not imagined outcomes. If a PR title suggests a feature, you can describe building that feature, but don't invent usage statistics or business metrics."#,
-        date_range,
-        org_name,
-        prs.len(),
-        pr_list
-    );
+    let generation = match generation {
+        Ok(generation) => generation,
+        Err(e) => return AiResult::failed(e),
+    };
 
-    let client = reqwest::Client::new();
+    if generation.text.is_empty() {
+        emit_ai_progress(AiPhase::Done, generation.tokens, 0, 0);
+        return AiResult::failed(if generation.stopped {
+            AppError::cancelled()
+        } else {
+            AppError::new(ErrorCode::Api, "No response from AI")
+        });
+    }
 
-    let request = OpenAiRequest {
-        model: "gpt-4o-mini".to_string(),
-        messages: vec![OpenAiMessage {
-            role: "user".to_string(),
-            content: prompt,
-        }],
-        max_tokens: 2000,
-        temperature: 0.7,
+    let (summary, structured) = match format {
+        SummaryFormat::Markdown => (generation.text, None),
+        SummaryFormat::Json if generation.stopped => {
+            emit_ai_progress(AiPhase::Done, generation.tokens, 0, 0);
+            return AiResult::failed(AppError::cancelled());
+        }
+        SummaryFormat::Json => match structured::parse(&generation.text, &prs) {
+            Ok(parsed) => (
+                structured::to_markdown(&parsed, language, &prs),
+                Some(parsed),
+            ),
+            Err(e) => {
+                emit_ai_progress(AiPhase::Done, generation.tokens, 0, 0);
+                return AiResult::failed(e);
+            }
+        },
     };
 
-    match client
-        .post("https://api.openai.com/v1/chat/completions")
-        .header("Authorization", format!("Bearer {}", api_key))
-        .header("Content-Type", "application/json")
-        .json(&request)
-        .send()
-        .await
-    {
-        Ok(response) => {
-            if response.status().is_success() {
-                match response.json::<OpenAiResponse>().await {
-                    Ok(ai_response) => {
-                        if let Some(choice) = ai_response.choices.first() {
-                            AiResult {
-                                success: true,
-                                summary: Some(choice.message.content.clone()),
-                                error: None,
-                            }
-                        } else {
-                            AiResult {
-                                success: false,
-                                summary: None,
-                                error: Some("No response from AI".to_string()),
-                            }
-                        }
-                    }
-                    Err(e) => AiResult {
-                        success: false,
-                        summary: None,
-                        error: Some(format!("Failed to parse AI response: {}", e)),
-                    },
+    let mut stopped = generation.stopped;
+    let mut evidence = None;
+    let mut error = None;
+    if let Some(rubric) = rubric.as_ref().filter(|_| !stopped) {
+        let batches: Vec<&[PullRequest]> = prs.chunks(MAPPING_BATCH_SIZE).collect();
+        let mut mapped = rubric::empty_evidence(rubric);
+
+        for (done, batch) in batches.iter().enumerate() {
+            emit_ai_progress(AiPhase::Mapping, 0, done, batches.len());
+            let prompt = rubric::mapping_prompt(rubric, level.as_deref(), language, batch);
+            let reply = llm::generate(
+                provider.as_ref(),
+                &prompt,
+                MAPPING_MAX_TOKENS,
+                &token,
+                |_, tokens| emit_ai_progress(AiPhase::Mapping, tokens, done, batches.len()),
+            )
+            .await;
+
+            let result = reply.and_then(|reply| {
+                if reply.stopped {
+                    Err(AppError::cancelled())
+                } else {
+                    rubric::add_mappings(&mut mapped, batch, &reply.text)
                 }
-            } else {
-                let status = response.status();
-                let error_text = response.text().await.unwrap_or_default();
-                AiResult {
-                    success: false,
-                    summary: None,
-                    error: Some(format!("OpenAI API error ({}): {}", status, error_text)),
+            });
+            match result {
+                Ok(()) => {}
+                Err(e) if e.code == ErrorCode::Cancelled => {
+                    stopped = true;
+                    break;
+                }
+                Err(e) => {
+                    error = Some(e);
+                    break;
                 }
             }
         }
-        Err(e) => AiResult {
+
+        if !stopped && error.is_none() {
+            evidence = Some(mapped);
+        }
+    }
+    emit_ai_progress(AiPhase::Done, generation.tokens, 0, 0);
+
+    AiResult {
+        success: true,
+        summary: Some(summary),
+        stopped,
+        rubric: evidence,
+        structured,
+        error,
+    }
+}
+
+#[tauri::command]
+fn list_prompt_templates() -> PromptTemplatesResult {
+    match templates::list(&get_templates_dir()) {
+        Ok(templates) => PromptTemplatesResult {
+            success: true,
+            templates: Some(templates),
+            error: None,
+        },
+        Err(e) => PromptTemplatesResult {
             success: false,
-            summary: None,
-            error: Some(format!("Failed to call OpenAI API: {}", e)),
+            templates: None,
+            error: Some(e),
+        },
+    }
+}
+
+#[tauri::command]
+fn save_prompt_template(template: PromptTemplate) -> SaveReportResult {
+    match templates::save(&get_templates_dir(), &template.name, &template.content) {
+        Ok(()) => SaveReportResult {
+            success: true,
+            error: None,
+        },
+        Err(e) => SaveReportResult {
+            success: false,
+            error: Some(e),
+        },
+    }
+}
+
+#[tauri::command]
+fn delete_prompt_template(name: String) -> SaveReportResult {
+    match templates::delete(&get_templates_dir(), &name) {
+        Ok(()) => SaveReportResult {
+            success: true,
+            error: None,
+        },
+        Err(e) => SaveReportResult {
+            success: false,
+            error: Some(e),
         },
     }
 }
 
+#[tauri::command]
+async fn list_ai_models(
+    api_key: Option<String>,
+    provider: Option<AiProvider>,
+    base_url: Option<String>,
+) -> ModelsResult {
+    let settings = read_settings();
+    let provider = provider.unwrap_or(settings.ai_provider);
+    let base_url = base_url
+        .or(settings.ai_base_url)
+        .filter(|url| !url.is_empty());
+
+    let models = match provider {
+        AiProvider::Ollama => {
+            llm::list_ollama_models(base_url.as_deref().unwrap_or(llm::DEFAULT_OLLAMA_URL)).await
+        }
+        AiProvider::OpenaiCompatible => match base_url {
+            Some(base_url) => {
+                llm::list_openai_compatible_models(&base_url, api_key.as_deref()).await
+            }
+            None => Err(AppError::new(
+                ErrorCode::Config,
+                "The \"openai_compatible\" AI provider requires `ai_base_url` in settings",
+            )),
+        },
+        _ => Err(AppError::new(
+            ErrorCode::InvalidInput,
+            "Model discovery is only available for local AI servers",
+        )),
+    };
+
+    match models {
+        Ok(models) => ModelsResult {
+            success: true,
+            models: Some(models),
+            error: None,
+        },
+        Err(error) => ModelsResult {
+            success: false,
+            models: None,
+            error: Some(error),
+        },
+    }
+}
+
+#[tauri::command]
+fn check_environment() -> EnvironmentReport {
+    environment::check(&get_data_dir(), &read_settings())
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     tauri::Builder::default()
+        .manage(InFlight::default())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_os::init())
         .plugin(tauri_plugin_dialog::init())
@@ -729,12 +1569,22 @@ pub fn run() {
             fetch_organizations,
             fetch_github_activity,
             fetch_reviewed_prs,
+            fetch_issues,
+            fetch_local_activity,
             generate_ai_summary,
+            list_ai_models,
+            list_prompt_templates,
+            save_prompt_template,
+            delete_prompt_template,
             save_settings,
+            update_settings,
             load_settings,
             save_report,
             load_reports,
-            delete_report
+            delete_report,
+            clear_activity_cache,
+            cancel_fetch,
+            check_environment
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -743,6 +1593,72 @@ pub fn run() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::cell::RefCell;
+
+    struct FakeSource {
+        prs: Vec<PullRequest>,
+        error: Option<AppError>,
+        owners: RefCell<Vec<Option<String>>>,
+    }
+
+    impl FakeSource {
+        fn with_prs(prs: Vec<PullRequest>) -> Self {
+            Self {
+                prs,
+                error: None,
+                owners: RefCell::new(Vec::new()),
+            }
+        }
+
+        fn failing(error: &str) -> Self {
+            Self {
+                prs: Vec::new(),
+                error: Some(AppError::from_gh_stderr(error)),
+                owners: RefCell::new(Vec::new()),
+            }
+        }
+    }
+
+    impl ActivitySource for FakeSource {
+        fn authored_prs(
+            &self,
+            _date_range: &str,
+            owner: Option<&str>,
+        ) -> Result<Vec<PullRequest>, AppError> {
+            self.owners.borrow_mut().push(owner.map(|o| o.to_string()));
+            match &self.error {
+                Some(e) => Err(e.clone()),
+                None => Ok(self.prs.clone()),
+            }
+        }
+
+        fn reviewed_prs(&self, _date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+            match &self.error {
+                Some(e) => Err(e.clone()),
+                None => Ok(Vec::new()),
+            }
+        }
+    }
+
+    fn sample_pr(title: &str, name_with_owner: &str) -> PullRequest {
+        PullRequest {
+            title: title.to_string(),
+            url: format!("https://github.com/{}/pull/1", name_with_owner),
+            body: None,
+            closed_at: "2024-11-15T10:00:00Z".to_string(),
+            created_at: None,
+            number: Some(1),
+            repository: Repository {
+                name: name_with_owner.split('/').nth(1).unwrap().to_string(),
+                name_with_owner: name_with_owner.to_string(),
+                host: None,
+            },
+            size: None,
+            labels: Vec::new(),
+            milestone: None,
+            closing_issues: Vec::new(),
+        }
+    }
 
     #[test]
     fn test_get_data_dir() {
@@ -774,7 +1690,12 @@ mod tests {
             repository: Repository {
                 name: "repo".to_string(),
                 name_with_owner: "org/repo".to_string(),
+                host: None,
             },
+            size: None,
+            labels: Vec::new(),
+            milestone: None,
+            closing_issues: Vec::new(),
         };
 
         let json = serde_json::to_string(&pr).unwrap();
@@ -795,14 +1716,68 @@ mod tests {
             repository: Repository {
                 name: "repo".to_string(),
                 name_with_owner: "org/repo".to_string(),
+                host: None,
             },
+            review: None,
         };
 
         let json = serde_json::to_string(&pr).unwrap();
         assert!(json.contains("Reviewed PR"));
+        assert!(!json.contains("review\""));
         assert!(json.contains("teammate"));
     }
 
+    #[test]
+    fn test_pr_context_lists_labels_and_closing_issues() {
+        let mut pr = sample_pr("Speed up checkout", "org/shop");
+        pr.labels = vec!["perf".to_string(), "incident".to_string()];
+        pr.milestone = Some("Q4".to_string());
+        pr.closing_issues = vec![IssueReference {
+            number: 123,
+            title: "Checkout is slow".to_string(),
+            url: "https://github.com/org/shop/issues/123".to_string(),
+        }];
+
+        assert_eq!(
+            pr_context(&pr),
+            "\n  Labels: perf, incident\n  Milestone: Q4\n  Closes: #123 Checkout is slow"
+        );
+        assert!(pr_context(&sample_pr("Fix typo", "org/shop")).is_empty());
+    }
+
+    #[test]
+    fn test_review_section_describes_review_depth() {
+        let pr = ReviewedPullRequest {
+            title: "Reviewed PR".to_string(),
+            url: "https://github.com/org/repo/pull/2".to_string(),
+            closed_at: Some("2024-11-15T12:00:00Z".to_string()),
+            created_at: "2024-11-13T09:00:00Z".to_string(),
+            author: Author {
+                login: "teammate".to_string(),
+            },
+            repository: Repository {
+                name: "repo".to_string(),
+                name_with_owner: "org/repo".to_string(),
+                host: None,
+            },
+            review: Some(ReviewDetails {
+                comment_count: 6,
+                state: Some(ReviewState::ChangesRequested),
+                requested_at: Some("2024-11-13T10:00:00Z".to_string()),
+                first_reviewed_at: Some("2024-11-13T11:30:00Z".to_string()),
+                minutes_to_first_review: Some(90),
+            }),
+        };
+
+        let section = review_section(&[pr]);
+
+        assert!(section.contains("reviewed these 1 merged pull requests"));
+        assert!(section.contains(
+            "**Reviewed PR** (repo, by teammate): requested changes, 6 review comments, first review 1.5 hours after being requested"
+        ));
+        assert!(review_section(&[]).is_empty());
+    }
+
     #[test]
     fn test_saved_report_serialization() {
         let report = SavedReport {
@@ -825,6 +1800,7 @@ mod tests {
     fn test_app_settings_serialization() {
         let settings = AppSettings {
             api_key: Some("sk-test-key".to_string()),
+            ..Default::default()
         };
 
         let json = serde_json::to_string(&settings).unwrap();
@@ -834,18 +1810,252 @@ mod tests {
 
     #[test]
     fn test_app_settings_empty() {
-        let settings = AppSettings { api_key: None };
+        let settings = AppSettings::default();
 
         let json = serde_json::to_string(&settings).unwrap();
         let parsed: AppSettings = serde_json::from_str(&json).unwrap();
         assert!(parsed.api_key.is_none());
     }
 
+    #[test]
+    fn test_app_settings_defaults_missing_fields() {
+        let parsed: AppSettings = serde_json::from_str(r#"{"api_key": "sk-test-key"}"#).unwrap();
+
+        assert_eq!(parsed.api_key, Some("sk-test-key".to_string()));
+        assert!(parsed.github_token.is_none());
+        assert_eq!(parsed.data_source, DataSource::GhCli);
+        assert_eq!(parsed.ai_provider, AiProvider::Openai);
+    }
+
+    #[test]
+    fn test_data_source_serialization() {
+        let json = serde_json::to_string(&DataSource::GithubApi).unwrap();
+        assert_eq!(json, r#""github_api""#);
+    }
+
+    #[test]
+    fn test_organizations_from_dedupes_owners() {
+        let source = FakeSource::with_prs(vec![
+            sample_pr("A", "zeta/api"),
+            sample_pr("B", "acme/web"),
+            sample_pr("C", "acme/api"),
+        ]);
+
+        let result = organizations_from(&source, "2024-01-01", "2024-12-31");
+
+        assert!(result.success);
+        assert!(result.complete);
+        assert_eq!(
+            result.organizations,
+            Some(vec!["acme".to_string(), "zeta".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_activity_from_ignores_empty_org() {
+        let source = FakeSource::with_prs(vec![sample_pr("A", "acme/web")]);
+
+        let result = activity_from(&source, "2024-01-01", "2024-12-31", Some(""));
+
+        assert!(result.success);
+        assert_eq!(result.data.unwrap().len(), 1);
+        assert_eq!(*source.owners.borrow(), vec![None]);
+    }
+
+    #[test]
+    fn test_activity_from_passes_org() {
+        let source = FakeSource::with_prs(vec![]);
+
+        activity_from(&source, "2024-01-01", "2024-12-31", Some("acme"));
+
+        assert_eq!(*source.owners.borrow(), vec![Some("acme".to_string())]);
+    }
+
+    #[test]
+    fn test_reviewed_from_reports_source_error() {
+        let source = FakeSource::failing("API rate limit exceeded");
+
+        let result = reviewed_from(&source, "2024-01-01", "2024-12-31");
+
+        assert!(!result.success);
+        assert!(result.data.is_none());
+        let error = result.error.unwrap();
+        assert_eq!(error.code, ErrorCode::RateLimited);
+        assert_eq!(error.message, "GitHub CLI error: API rate limit exceeded");
+    }
+
+    #[test]
+    fn test_activity_source_requires_fixture_dir() {
+        let settings = AppSettings {
+            data_source: DataSource::Fixture,
+            ..Default::default()
+        };
+
+        assert!(activity_source(&settings).is_err());
+    }
+
+    #[test]
+    fn test_llm_provider_from_settings() {
+        let azure = AppSettings {
+            ai_provider: AiProvider::AzureOpenai,
+            ai_base_url: Some("https://my-team.openai.azure.com".to_string()),
+            ..Default::default()
+        };
+        let compatible = AppSettings {
+            ai_provider: AiProvider::OpenaiCompatible,
+            ai_base_url: Some("http://localhost:8000/v1".to_string()),
+            ai_model: Some("qwen2.5".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            llm_provider(&AppSettings::default(), "")
+                .err()
+                .unwrap()
+                .code,
+            ErrorCode::Config
+        );
+        assert_eq!(
+            llm_provider(&azure, "key").err().unwrap().message,
+            "The \"azure_openai\" AI provider requires `ai_model` in settings"
+        );
+        assert_eq!(
+            llm_provider(&compatible, "").unwrap().name(),
+            "OpenAI-compatible API"
+        );
+        assert!(
+            llm_provider(
+                &AppSettings {
+                    ai_provider: AiProvider::Ollama,
+                    ai_model: Some("llama3.2".to_string()),
+                    ..Default::default()
+                },
+                ""
+            )
+            .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_github_hosts_default_to_github_com() {
+        let settings = AppSettings {
+            github_token: Some("ghp_test".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            settings.github_hosts(),
+            vec![GitHubHost {
+                host: "github.com".to_string(),
+                token: Some("ghp_test".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_gitlab_instance_host() {
+        let instance = GitLabInstance {
+            url: "https://gitlab.acme.com/".to_string(),
+            token: "glpat-test".to_string(),
+        };
+
+        assert_eq!(instance.host(), "gitlab.acme.com");
+    }
+
+    #[test]
+    fn test_repository_host_defaults_to_none() {
+        let json = r#"{"name": "repo", "nameWithOwner": "org/repo"}"#;
+        let repo: Repository = serde_json::from_str(json).unwrap();
+
+        assert!(repo.host.is_none());
+        assert!(!serde_json::to_string(&repo).unwrap().contains("host"));
+    }
+
+    #[test]
+    fn test_issue_deserialization_defaults_involvement() {
+        let json = r#"{
+            "title": "Flaky checkout test",
+            "url": "https://github.com/org/repo/issues/9",
+            "body": null,
+            "state": "closed",
+            "createdAt": "2024-11-01T08:00:00Z",
+            "closedAt": "2024-11-02T08:00:00Z",
+            "number": 9,
+            "repository": {"name": "repo", "nameWithOwner": "org/repo"}
+        }"#;
+        let issue: Issue = serde_json::from_str(json).unwrap();
+
+        assert_eq!(issue.involvement, IssueInvolvement::Opened);
+        assert_eq!(issue.closed_at.as_deref(), Some("2024-11-02T08:00:00Z"));
+    }
+
+    #[test]
+    fn test_issues_from_prefers_closed_involvement() {
+        struct IssueSource;
+
+        impl ActivitySource for IssueSource {
+            fn authored_prs(
+                &self,
+                _date_range: &str,
+                _owner: Option<&str>,
+            ) -> Result<Vec<PullRequest>, AppError> {
+                Ok(Vec::new())
+            }
+
+            fn reviewed_prs(
+                &self,
+                _date_range: &str,
+            ) -> Result<Vec<ReviewedPullRequest>, AppError> {
+                Ok(Vec::new())
+            }
+
+            fn issues(
+                &self,
+                _date_range: &str,
+                _owner: Option<&str>,
+                involvement: IssueInvolvement,
+            ) -> Result<Vec<Issue>, AppError> {
+                let url = match involvement {
+                    IssueInvolvement::Commented => "https://github.com/org/repo/issues/2",
+                    _ => "https://github.com/org/repo/issues/1",
+                };
+                Ok(vec![Issue {
+                    title: "Issue".to_string(),
+                    url: url.to_string(),
+                    body: None,
+                    state: "closed".to_string(),
+                    created_at: "2024-11-01T08:00:00Z".to_string(),
+                    closed_at: None,
+                    number: None,
+                    repository: Repository {
+                        name: "repo".to_string(),
+                        name_with_owner: "org/repo".to_string(),
+                        host: None,
+                    },
+                    involvement: IssueInvolvement::Opened,
+                }])
+            }
+        }
+
+        let result = issues_from(&IssueSource, "2024-01-01", "2024-12-31", None);
+        let issues = result.data.unwrap();
+
+        assert!(result.success);
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].involvement, IssueInvolvement::Closed);
+        assert_eq!(issues[1].involvement, IssueInvolvement::Commented);
+    }
+
     #[test]
     fn test_fetch_result_success() {
         let result = FetchResult {
             success: true,
             data: Some(vec![]),
+            complete: true,
+            stale: false,
+            as_of: None,
+            size_stats: Some(SizeStats::default()),
+            label_stats: Some(LabelStats::default()),
             error: None,
         };
 
@@ -859,12 +2069,17 @@ mod tests {
         let result = FetchResult {
             success: false,
             data: None,
-            error: Some("Error message".to_string()),
+            complete: false,
+            stale: false,
+            as_of: None,
+            size_stats: None,
+            label_stats: None,
+            error: Some(AppError::new(ErrorCode::Network, "Error message")),
         };
 
         assert!(!result.success);
         assert!(result.data.is_none());
-        assert_eq!(result.error, Some("Error message".to_string()));
+        assert_eq!(result.error.unwrap().message, "Error message");
     }
 
     #[test]
@@ -872,6 +2087,9 @@ mod tests {
         let result = AiResult {
             success: true,
             summary: Some("AI summary".to_string()),
+            stopped: false,
+            rubric: None,
+            structured: None,
             error: None,
         };
 
@@ -884,6 +2102,9 @@ mod tests {
         let result = OrganizationsResult {
             success: true,
             organizations: Some(vec!["org1".to_string(), "org2".to_string()]),
+            complete: true,
+            stale: false,
+            as_of: None,
             error: None,
         };
 
@@ -918,7 +2139,7 @@ mod tests {
     fn test_load_settings_result() {
         let result = LoadSettingsResult {
             success: true,
-            settings: Some(AppSettings { api_key: None }),
+            settings: Some(AppSettings::default()),
             error: None,
         };
 
@@ -944,13 +2165,12 @@ mod tests {
     }
 
     #[test]
-    fn test_find_gh_cli_returns_option() {
+    fn test_find_gh_cli_returns_result() {
         let result = find_gh_cli();
-        // Result could be Some or None depending on the environment
+        // Result could be Ok or Err depending on the environment
         // Just verify it doesn't panic
-        match result {
-            Some(path) => assert!(path.exists()),
-            None => (),
+        if let Ok(path) = result {
+            assert!(path.exists());
         }
     }
 }
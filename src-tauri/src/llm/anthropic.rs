@@ -0,0 +1,188 @@
+use super::{LlmProvider, StreamEvent, TEMPERATURE};
+use crate::error::{AppError, ErrorCode};
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+
+const MESSAGES_URL: &str = "https://api.anthropic.com/v1/messages";
+const API_VERSION: &str = "2023-06-01";
+const DEFAULT_MODEL: &str = "claude-haiku-4-5";
+
+#[derive(Debug, Serialize)]
+struct MessagesRequest<'a> {
+    model: &'a str,
+    messages: Vec<Message<'a>>,
+    max_tokens: u32,
+    temperature: f32,
+    stream: bool,
+}
+
+#[derive(Debug, Serialize)]
+struct Message<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+/// The streamed events the summary needs; pings and block boundaries are ignored.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum Event {
+    ContentBlockDelta {
+        delta: Delta,
+    },
+    MessageDelta {
+        usage: Usage,
+    },
+    MessageStop,
+    Error {
+        error: ErrorDetails,
+    },
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Debug, Deserialize)]
+struct Delta {
+    /// Only set on `text_delta`s.
+    text: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Usage {
+    output_tokens: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ErrorDetails {
+    #[serde(rename = "type")]
+    kind: String,
+    message: String,
+}
+
+/// The Anthropic Messages API.
+#[derive(Debug)]
+pub struct AnthropicProvider {
+    api_key: String,
+    model: String,
+}
+
+impl AnthropicProvider {
+    pub fn new(api_key: &str, model: Option<&str>) -> Self {
+        Self {
+            api_key: api_key.to_string(),
+            model: model.unwrap_or(DEFAULT_MODEL).to_string(),
+        }
+    }
+}
+
+impl LlmProvider for AnthropicProvider {
+    fn name(&self) -> &'static str {
+        "Anthropic API"
+    }
+
+    fn request(&self, client: &Client, prompt: &str, max_tokens: u32) -> RequestBuilder {
+        client
+            .post(MESSAGES_URL)
+            .header("x-api-key", &self.api_key)
+            .header("anthropic-version", API_VERSION)
+            .json(&MessagesRequest {
+                model: &self.model,
+                messages: vec![Message {
+                    role: "user",
+                    content: prompt,
+                }],
+                max_tokens,
+                temperature: TEMPERATURE,
+                stream: true,
+            })
+    }
+
+    fn parse_event(&self, data: &str) -> Result<StreamEvent, AppError> {
+        let event: Event = serde_json::from_str(data).map_err(|e| {
+            AppError::new(
+                ErrorCode::Parse,
+                format!("Failed to parse AI response: {}", e),
+            )
+        })?;
+
+        Ok(match event {
+            Event::ContentBlockDelta { delta } => StreamEvent {
+                text: delta.text.unwrap_or_default(),
+                ..StreamEvent::default()
+            },
+            Event::MessageDelta { usage } => StreamEvent {
+                output_tokens: Some(usage.output_tokens),
+                ..StreamEvent::default()
+            },
+            Event::MessageStop => StreamEvent {
+                done: true,
+                ..StreamEvent::default()
+            },
+            Event::Error { error } => {
+                let code = match error.kind.as_str() {
+                    "rate_limit_error" => ErrorCode::RateLimited,
+                    _ => ErrorCode::Api,
+                };
+                return Err(AppError::new(
+                    code,
+                    format!("Anthropic API error: {}", error.message),
+                ));
+            }
+            Event::Other => StreamEvent::default(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_events() {
+        let provider = AnthropicProvider::new("key", None);
+
+        let text = provider
+            .parse_event(r#"{"type":"content_block_delta","index":0,"delta":{"type":"text_delta","text":"Built"}}"#)
+            .unwrap();
+        let usage = provider
+            .parse_event(r#"{"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":412}}"#)
+            .unwrap();
+        let error = provider
+            .parse_event(
+                r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+            )
+            .unwrap_err();
+
+        assert_eq!(text.text, "Built");
+        assert_eq!(usage.output_tokens, Some(412));
+        assert!(
+            provider
+                .parse_event(r#"{"type":"message_stop"}"#)
+                .unwrap()
+                .done
+        );
+        assert_eq!(
+            provider.parse_event(r#"{"type":"ping"}"#).unwrap(),
+            StreamEvent::default()
+        );
+        assert_eq!(error.code, ErrorCode::Api);
+        assert_eq!(error.message, "Anthropic API error: Overloaded");
+    }
+
+    #[test]
+    fn test_request_headers() {
+        let provider = AnthropicProvider::new("key", Some("claude-sonnet-4-5"));
+
+        let request = provider
+            .request(&Client::new(), "Summarize", 2000)
+            .build()
+            .unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+
+        assert_eq!(request.url().as_str(), MESSAGES_URL);
+        assert_eq!(request.headers()["x-api-key"], "key");
+        assert_eq!(request.headers()["anthropic-version"], API_VERSION);
+        assert_eq!(body["model"], "claude-sonnet-4-5");
+        assert_eq!(body["stream"], true);
+    }
+}
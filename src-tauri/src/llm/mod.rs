@@ -0,0 +1,252 @@
+mod anthropic;
+mod ollama;
+mod openai;
+
+pub use anthropic::AnthropicProvider;
+pub use ollama::{
+    DEFAULT_BASE_URL as DEFAULT_OLLAMA_URL, OllamaProvider, list_models as list_ollama_models,
+};
+pub use openai::{
+    DEFAULT_AZURE_API_VERSION, OpenAiProvider, list_models as list_openai_compatible_models,
+};
+
+use crate::cancel::CancelToken;
+use crate::chunking::DEFAULT_PROMPT_TOKEN_BUDGET;
+use crate::error::{AppError, ErrorCode};
+use crate::sse::SseParser;
+use reqwest::{Client, RequestBuilder};
+use serde::de::DeserializeOwned;
+
+/// Sampling temperature used for every summary.
+const TEMPERATURE: f32 = 0.7;
+
+/// What one server-sent event of a streamed completion carried.
+#[derive(Debug, Default, PartialEq)]
+pub struct StreamEvent {
+    /// Text to append to the completion.
+    pub text: String,
+    /// Completion tokens generated so far, when the provider reports them.
+    pub output_tokens: Option<u32>,
+    /// `true` once the provider has signalled the end of the stream.
+    pub done: bool,
+}
+
+/// How a streamed response body is split into events.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StreamFormat {
+    /// `text/event-stream`, one JSON payload per `data:` event.
+    Sse,
+    /// One JSON object per line.
+    JsonLines,
+}
+
+/// A chat API that can stream a completion of a single-message prompt.
+pub trait LlmProvider: Send + Sync {
+    /// Name used in error messages, e.g. `OpenAI API`.
+    fn name(&self) -> &'static str;
+
+    fn stream_format(&self) -> StreamFormat {
+        StreamFormat::Sse
+    }
+
+    /// Estimated tokens of data one summary prompt may hold before PRs are
+    /// summarized in chunks first.
+    fn prompt_token_budget(&self) -> usize {
+        DEFAULT_PROMPT_TOKEN_BUDGET
+    }
+
+    /// Builds the streaming request for a completion of `prompt`.
+    fn request(&self, client: &Client, prompt: &str, max_tokens: u32) -> RequestBuilder;
+
+    /// Parses the data of one server-sent event of the response.
+    fn parse_event(&self, data: &str) -> Result<StreamEvent, AppError>;
+}
+
+/// Splits response bytes into the payloads [`LlmProvider::parse_event`] takes.
+enum EventSplitter {
+    Sse(SseParser),
+    JsonLines(Vec<u8>),
+}
+
+impl EventSplitter {
+    fn new(format: StreamFormat) -> Self {
+        match format {
+            StreamFormat::Sse => Self::Sse(SseParser::default()),
+            StreamFormat::JsonLines => Self::JsonLines(Vec::new()),
+        }
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        match self {
+            Self::Sse(parser) => parser.push(bytes),
+            Self::JsonLines(buffer) => {
+                buffer.extend_from_slice(bytes);
+                let mut lines = Vec::new();
+                while let Some(end) = buffer.iter().position(|&b| b == b'\n') {
+                    let line: Vec<u8> = buffer.drain(..=end).collect();
+                    let line = String::from_utf8_lossy(&line).trim().to_string();
+                    if !line.is_empty() {
+                        lines.push(line);
+                    }
+                }
+                lines
+            }
+        }
+    }
+}
+
+/// Sends `request` and decodes its JSON response, for the non-streaming calls
+/// like model discovery.
+async fn get_json<T: DeserializeOwned>(
+    service: &str,
+    request: RequestBuilder,
+) -> Result<T, AppError> {
+    let response = request
+        .send()
+        .await
+        .map_err(|e| AppError::from_request(service, e))?;
+
+    if !response.status().is_success() {
+        let status = response.status();
+        let headers = response.headers().clone();
+        let error_text = response.text().await.unwrap_or_default();
+        return Err(AppError::from_response_parts(
+            service,
+            status,
+            &headers,
+            &error_text,
+        ));
+    }
+
+    response.json().await.map_err(|e| {
+        AppError::new(
+            ErrorCode::Parse,
+            format!("Failed to parse {} response: {}", service, e),
+        )
+    })
+}
+
+/// A finished, or stopped, completion.
+#[derive(Debug)]
+pub struct Generation {
+    pub text: String,
+    pub tokens: u32,
+    /// `true` when `token` was cancelled before the provider finished.
+    pub stopped: bool,
+}
+
+/// Streams a completion of `prompt` from `provider`, passing each piece of text
+/// and the token count so far to `on_text`.
+///
+/// Cancelling `token` before the response starts fails with a `Cancelled`
+/// error; cancelling it mid-stream keeps what was generated so far. A response
+/// that ends before the provider signals it is done fails with an `Api` error.
+pub async fn generate(
+    provider: &dyn LlmProvider,
+    prompt: &str,
+    max_tokens: u32,
+    token: &CancelToken,
+    mut on_text: impl FnMut(&str, u32),
+) -> Result<Generation, AppError> {
+    let client = Client::new();
+    let send = provider.request(&client, prompt, max_tokens).send();
+
+    let response = tokio::select! {
+        response = send => response,
+        _ = token.cancelled() => return Err(AppError::cancelled()),
+    };
+    let mut response = match response {
+        Ok(response) if response.status().is_success() => response,
+        Ok(response) => {
+            let status = response.status();
+            let headers = response.headers().clone();
+            let error_text = response.text().await.unwrap_or_default();
+            return Err(AppError::from_response_parts(
+                provider.name(),
+                status,
+                &headers,
+                &error_text,
+            ));
+        }
+        Err(e) => return Err(AppError::from_request(provider.name(), e)),
+    };
+
+    let mut splitter = EventSplitter::new(provider.stream_format());
+    let mut generation = Generation {
+        text: String::new(),
+        tokens: 0,
+        stopped: false,
+    };
+
+    'stream: loop {
+        let bytes = tokio::select! {
+            chunk = response.chunk() => match chunk {
+                Ok(Some(bytes)) => bytes,
+                Ok(None) => {
+                    return Err(AppError::new(
+                        ErrorCode::Api,
+                        format!("{} ended the response before it finished", provider.name()),
+                    ));
+                }
+                Err(e) => return Err(AppError::from_request(provider.name(), e)),
+            },
+            _ = token.cancelled() => {
+                generation.stopped = true;
+                break;
+            }
+        };
+
+        for data in splitter.push(&bytes) {
+            let event = provider.parse_event(&data)?;
+
+            if let Some(tokens) = event.output_tokens {
+                generation.tokens = tokens;
+            }
+            if !event.text.is_empty() {
+                // Each text event is roughly one token; reported usage corrects the count.
+                generation.tokens += 1;
+                generation.text.push_str(&event.text);
+                on_text(&event.text, generation.tokens);
+            }
+            if event.done {
+                break 'stream;
+            }
+        }
+    }
+
+    Ok(generation)
+}
+
+/// The JSON object in a model's reply, without the Markdown fences or prose
+/// models tend to wrap it in.
+pub fn json_body(text: &str) -> &str {
+    match (text.find('{'), text.rfind('}')) {
+        (Some(start), Some(end)) if start < end => &text[start..=end],
+        _ => text.trim(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines_split_across_chunks() {
+        let mut splitter = EventSplitter::new(StreamFormat::JsonLines);
+
+        assert!(splitter.push(b"{\"a\":").is_empty());
+        assert_eq!(
+            splitter.push(b"1}\n\n{\"b\":2}\r\n{\"c\""),
+            vec!["{\"a\":1}", "{\"b\":2}"]
+        );
+    }
+
+    #[test]
+    fn test_json_body_strips_fences() {
+        assert_eq!(
+            json_body("Here you go:\n```json\n{\"a\": {\"b\": 1}}\n```"),
+            r#"{"a": {"b": 1}}"#
+        );
+        assert_eq!(json_body(" not json "), "not json");
+    }
+}
@@ -0,0 +1,204 @@
+use super::{LlmProvider, StreamEvent, StreamFormat, TEMPERATURE};
+use crate::chunking::estimate_tokens;
+use crate::error::{AppError, ErrorCode};
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+
+pub const DEFAULT_BASE_URL: &str = "http://localhost:11434";
+
+/// Local models usually run with much smaller context windows than hosted ones,
+/// so less data goes into one prompt before it is chunked.
+const PROMPT_TOKEN_BUDGET: usize = 6_000;
+/// Context windows are requested in steps of this many tokens.
+const CONTEXT_STEP: u32 = 1024;
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    stream: bool,
+    options: Options,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct Options {
+    temperature: f32,
+    num_predict: u32,
+    num_ctx: u32,
+}
+
+/// Context window that fits `prompt` and a completion of `max_tokens`.
+///
+/// Without one Ollama uses its default of a few thousand tokens and silently
+/// drops the start of longer prompts. The estimate gets a quarter on top, since
+/// code and non-English text take more tokens than it assumes.
+fn context_window(prompt: &str, max_tokens: u32) -> u32 {
+    let tokens = u32::try_from(estimate_tokens(prompt))
+        .unwrap_or(u32::MAX)
+        .saturating_add(max_tokens);
+    tokens
+        .saturating_add(tokens / 4)
+        .div_ceil(CONTEXT_STEP)
+        .saturating_mul(CONTEXT_STEP)
+}
+
+/// One line of a streamed `/api/chat` response.
+#[derive(Debug, Deserialize)]
+struct ChatLine {
+    message: Option<Message>,
+    #[serde(default)]
+    done: bool,
+    /// Tokens generated, sent on the final line.
+    eval_count: Option<u32>,
+    error: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Message {
+    content: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TagsResponse {
+    models: Vec<Tag>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tag {
+    name: String,
+}
+
+/// Ollama's native `/api/chat` endpoint.
+#[derive(Debug)]
+pub struct OllamaProvider {
+    base_url: String,
+    model: String,
+}
+
+impl OllamaProvider {
+    pub fn new(base_url: &str, model: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            model: model.to_string(),
+        }
+    }
+}
+
+impl LlmProvider for OllamaProvider {
+    fn name(&self) -> &'static str {
+        "Ollama"
+    }
+
+    fn stream_format(&self) -> StreamFormat {
+        StreamFormat::JsonLines
+    }
+
+    fn prompt_token_budget(&self) -> usize {
+        PROMPT_TOKEN_BUDGET
+    }
+
+    fn request(&self, client: &Client, prompt: &str, max_tokens: u32) -> RequestBuilder {
+        client
+            .post(format!("{}/api/chat", self.base_url))
+            .json(&ChatRequest {
+                model: &self.model,
+                messages: vec![ChatMessage {
+                    role: "user",
+                    content: prompt,
+                }],
+                stream: true,
+                options: Options {
+                    temperature: TEMPERATURE,
+                    num_predict: max_tokens,
+                    num_ctx: context_window(prompt, max_tokens),
+                },
+            })
+    }
+
+    fn parse_event(&self, data: &str) -> Result<StreamEvent, AppError> {
+        let line: ChatLine = serde_json::from_str(data).map_err(|e| {
+            AppError::new(
+                ErrorCode::Parse,
+                format!("Failed to parse AI response: {}", e),
+            )
+        })?;
+
+        if let Some(error) = line.error {
+            return Err(AppError::new(
+                ErrorCode::Api,
+                format!("Ollama error: {}", error),
+            ));
+        }
+
+        Ok(StreamEvent {
+            text: line.message.map(|m| m.content).unwrap_or_default(),
+            output_tokens: line.eval_count,
+            done: line.done,
+        })
+    }
+}
+
+/// Lists the models pulled into the Ollama server at `base_url`.
+pub async fn list_models(base_url: &str) -> Result<Vec<String>, AppError> {
+    let url = format!("{}/api/tags", base_url.trim_end_matches('/'));
+    let response: TagsResponse = super::get_json("Ollama", Client::new().get(url)).await?;
+
+    Ok(response.models.into_iter().map(|tag| tag.name).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_lines() {
+        let provider = OllamaProvider::new(DEFAULT_BASE_URL, "llama3.2");
+
+        let text = provider
+            .parse_event(r#"{"model":"llama3.2","message":{"role":"assistant","content":"Built"},"done":false}"#)
+            .unwrap();
+        let last = provider
+            .parse_event(r#"{"model":"llama3.2","message":{"role":"assistant","content":""},"done":true,"eval_count":298}"#)
+            .unwrap();
+        let error = provider
+            .parse_event(r#"{"error":"model \"llama3.2\" not found, try pulling it first"}"#)
+            .unwrap_err();
+
+        assert_eq!(text.text, "Built");
+        assert!(!text.done);
+        assert_eq!(last.output_tokens, Some(298));
+        assert!(last.done);
+        assert_eq!(error.code, ErrorCode::Api);
+    }
+
+    #[test]
+    fn test_request() {
+        let provider = OllamaProvider::new("http://localhost:11434/", "llama3.2");
+
+        let request = provider
+            .request(&Client::new(), "Summarize", 2000)
+            .build()
+            .unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+
+        assert_eq!(request.url().as_str(), "http://localhost:11434/api/chat");
+        assert_eq!(body["model"], "llama3.2");
+        assert_eq!(body["options"]["num_predict"], 2000);
+        assert_eq!(body["options"]["num_ctx"], 3072);
+    }
+
+    #[test]
+    fn test_context_window_fits_prompt_and_completion() {
+        let prompt = "x".repeat(40_000);
+
+        assert_eq!(context_window(&prompt, 2000), 15360);
+        assert_eq!(context_window("", 700), 1024);
+    }
+}
@@ -0,0 +1,272 @@
+use super::{LlmProvider, StreamEvent, TEMPERATURE};
+use crate::error::{AppError, ErrorCode};
+use reqwest::{Client, RequestBuilder};
+use serde::{Deserialize, Serialize};
+
+const OPENAI_URL: &str = "https://api.openai.com/v1/chat/completions";
+const DEFAULT_MODEL: &str = "gpt-4o-mini";
+pub const DEFAULT_AZURE_API_VERSION: &str = "2024-10-21";
+
+#[derive(Debug, Serialize)]
+struct ChatRequest<'a> {
+    /// Omitted for Azure OpenAI, where the deployment in the URL picks the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    model: Option<&'a str>,
+    messages: Vec<ChatMessage<'a>>,
+    max_tokens: u32,
+    temperature: f32,
+    stream: bool,
+    /// Only sent to OpenAI: Azure and other compatible servers may reject it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stream_options: Option<StreamOptions>,
+}
+
+#[derive(Debug, Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct StreamOptions {
+    include_usage: bool,
+}
+
+/// One `chat.completion.chunk` of a streamed response.
+#[derive(Debug, Deserialize)]
+struct Chunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+    usage: Option<Usage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Usage {
+    completion_tokens: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkChoice {
+    delta: Delta,
+}
+
+#[derive(Debug, Deserialize)]
+struct Delta {
+    content: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ModelList {
+    data: Vec<Model>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Model {
+    id: String,
+}
+
+/// The Chat Completions API, as served by OpenAI, Azure OpenAI or any
+/// OpenAI-compatible server.
+#[derive(Debug)]
+pub struct OpenAiProvider {
+    name: &'static str,
+    url: String,
+    /// Header carrying the API key, and its value. Local servers often need none.
+    auth: Option<(&'static str, String)>,
+    model: Option<String>,
+    /// Whether to ask for token usage at the end of the stream.
+    include_usage: bool,
+}
+
+impl OpenAiProvider {
+    pub fn openai(api_key: &str, model: Option<&str>) -> Self {
+        Self {
+            name: "OpenAI API",
+            url: OPENAI_URL.to_string(),
+            auth: Some(("Authorization", format!("Bearer {}", api_key))),
+            model: Some(model.unwrap_or(DEFAULT_MODEL).to_string()),
+            include_usage: true,
+        }
+    }
+
+    /// A deployment of an Azure OpenAI resource, e.g. `https://my-team.openai.azure.com`.
+    pub fn azure(endpoint: &str, deployment: &str, api_version: &str, api_key: &str) -> Self {
+        Self {
+            name: "Azure OpenAI",
+            url: format!(
+                "{}/openai/deployments/{}/chat/completions?api-version={}",
+                endpoint.trim_end_matches('/'),
+                deployment,
+                api_version
+            ),
+            auth: Some(("api-key", api_key.to_string())),
+            model: None,
+            include_usage: false,
+        }
+    }
+
+    /// A server exposing `{base_url}/chat/completions`, e.g. `http://localhost:8000/v1`.
+    pub fn compatible(base_url: &str, model: &str, api_key: Option<&str>) -> Self {
+        Self {
+            name: "OpenAI-compatible API",
+            url: format!("{}/chat/completions", base_url.trim_end_matches('/')),
+            auth: api_key
+                .filter(|key| !key.is_empty())
+                .map(|key| ("Authorization", format!("Bearer {}", key))),
+            model: Some(model.to_string()),
+            include_usage: false,
+        }
+    }
+}
+
+impl LlmProvider for OpenAiProvider {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn request(&self, client: &Client, prompt: &str, max_tokens: u32) -> RequestBuilder {
+        let request = client.post(&self.url).json(&ChatRequest {
+            model: self.model.as_deref(),
+            messages: vec![ChatMessage {
+                role: "user",
+                content: prompt,
+            }],
+            max_tokens,
+            temperature: TEMPERATURE,
+            stream: true,
+            stream_options: self.include_usage.then_some(StreamOptions {
+                include_usage: true,
+            }),
+        });
+
+        match &self.auth {
+            Some((header, value)) => request.header(*header, value),
+            None => request,
+        }
+    }
+
+    fn parse_event(&self, data: &str) -> Result<StreamEvent, AppError> {
+        if data.trim() == "[DONE]" {
+            return Ok(StreamEvent {
+                done: true,
+                ..StreamEvent::default()
+            });
+        }
+
+        let chunk: Chunk = serde_json::from_str(data).map_err(|e| {
+            AppError::new(
+                ErrorCode::Parse,
+                format!("Failed to parse AI response: {}", e),
+            )
+        })?;
+
+        Ok(StreamEvent {
+            text: chunk
+                .choices
+                .into_iter()
+                .filter_map(|choice| choice.delta.content)
+                .collect(),
+            output_tokens: chunk.usage.map(|usage| usage.completion_tokens),
+            done: false,
+        })
+    }
+}
+
+/// Lists the models served by the OpenAI-compatible server at `base_url`,
+/// e.g. the model a llama.cpp server was started with.
+pub async fn list_models(base_url: &str, api_key: Option<&str>) -> Result<Vec<String>, AppError> {
+    let mut request = Client::new().get(format!("{}/models", base_url.trim_end_matches('/')));
+    if let Some(key) = api_key.filter(|key| !key.is_empty()) {
+        request = request.header("Authorization", format!("Bearer {}", key));
+    }
+    let response: ModelList = super::get_json("OpenAI-compatible API", request).await?;
+
+    Ok(response.data.into_iter().map(|model| model.id).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_event() {
+        let provider = OpenAiProvider::openai("sk-test", None);
+
+        let chunk = provider
+            .parse_event(r#"{"choices":[{"index":0,"delta":{"content":"Built"}}],"usage":null}"#)
+            .unwrap();
+        let usage = provider
+            .parse_event(r#"{"choices":[],"usage":{"completion_tokens":412}}"#)
+            .unwrap();
+
+        assert_eq!(chunk.text, "Built");
+        assert_eq!(usage.output_tokens, Some(412));
+        assert!(provider.parse_event("[DONE]").unwrap().done);
+        assert_eq!(
+            provider.parse_event("{oops").unwrap_err().code,
+            ErrorCode::Parse
+        );
+    }
+
+    #[test]
+    fn test_azure_request() {
+        let provider = OpenAiProvider::azure(
+            "https://my-team.openai.azure.com/",
+            "summaries",
+            DEFAULT_AZURE_API_VERSION,
+            "azure-key",
+        );
+
+        let request = provider
+            .request(&Client::new(), "Summarize", 2000)
+            .build()
+            .unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "https://my-team.openai.azure.com/openai/deployments/summaries/chat/completions?api-version=2024-10-21"
+        );
+        assert_eq!(request.headers()["api-key"], "azure-key");
+        assert!(body.get("model").is_none());
+        assert!(body.get("stream_options").is_none());
+        assert_eq!(body["messages"][0]["content"], "Summarize");
+    }
+
+    #[test]
+    fn test_openai_request_asks_for_usage() {
+        let provider = OpenAiProvider::openai("sk-test", None);
+
+        let request = provider
+            .request(&Client::new(), "Summarize", 2000)
+            .build()
+            .unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+
+        assert_eq!(request.headers()["Authorization"], "Bearer sk-test");
+        assert_eq!(body["model"], DEFAULT_MODEL);
+        assert_eq!(body["stream_options"]["include_usage"], true);
+    }
+
+    #[test]
+    fn test_compatible_request_without_key() {
+        let provider = OpenAiProvider::compatible("http://localhost:8000/v1/", "qwen2.5", None);
+
+        let request = provider
+            .request(&Client::new(), "Summarize", 2000)
+            .build()
+            .unwrap();
+        let body: serde_json::Value =
+            serde_json::from_slice(request.body().unwrap().as_bytes().unwrap()).unwrap();
+
+        assert_eq!(
+            request.url().as_str(),
+            "http://localhost:8000/v1/chat/completions"
+        );
+        assert!(request.headers().get("Authorization").is_none());
+        assert_eq!(body["model"], "qwen2.5");
+        assert!(body.get("stream_options").is_none());
+    }
+}
@@ -0,0 +1,198 @@
+use serde::Serialize;
+use std::cell::RefCell;
+
+/// Event carrying a [`FetchProgress`] while pull requests are being fetched.
+pub const FETCH_PROGRESS_EVENT: &str = "fetch-progress";
+/// Event carrying an [`AiProgress`] while a summary is being generated.
+pub const AI_PROGRESS_EVENT: &str = "ai-progress";
+/// Event carrying each [`AiChunk`] of a summary as it streams in.
+pub const AI_CHUNK_EVENT: &str = "ai-chunk";
+
+thread_local! {
+    /// Progress of the fetch running on this blocking thread, if it is tracked.
+    static CURRENT: RefCell<Option<Tracker>> = const { RefCell::new(None) };
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FetchPhase {
+    /// Paging through search results.
+    Searching,
+    /// Looking up sizes, labels or review details for the results.
+    Enriching,
+    Done,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct FetchProgress {
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+    /// `authored` or `reviewed`.
+    pub kind: &'static str,
+    pub phase: FetchPhase,
+    #[serde(rename = "pagesFetched")]
+    pub pages_fetched: u32,
+    /// Results received so far, before de-duplication and owner filtering.
+    #[serde(rename = "itemsFetched")]
+    pub items_fetched: usize,
+}
+
+#[derive(Debug, Serialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AiPhase {
+    /// Writing notes on one chunk of a PR set too large for a single prompt.
+    Summarizing,
+    /// Waiting for or receiving the model's response.
+    Generating,
+    /// Matching PRs to the competencies of the career-ladder rubric.
+    Mapping,
+    Done,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AiProgress {
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+    pub phase: AiPhase,
+    /// Completion tokens generated so far in the current pass.
+    pub tokens: u32,
+    /// Parts of the current phase that are finished.
+    #[serde(rename = "chunksDone")]
+    pub chunks_done: usize,
+    /// Parts the current phase is split into: chunks of PRs while summarizing,
+    /// batches while mapping. 0 for a single pass.
+    pub chunks: usize,
+}
+
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub struct AiChunk {
+    #[serde(rename = "requestId")]
+    pub request_id: Option<String>,
+    /// Text to append to the summary.
+    pub delta: String,
+}
+
+struct Tracker {
+    progress: FetchProgress,
+    emit: Box<dyn Fn(&FetchProgress)>,
+}
+
+/// Runs `work` with progress tracking, passing every update to `emit`.
+///
+/// Sources deep in the fetch report pages through [`page_fetched`] and
+/// [`set_phase`]; both do nothing when no fetch is being tracked.
+pub fn track<T>(
+    kind: &'static str,
+    request_id: Option<String>,
+    emit: impl Fn(&FetchProgress) + 'static,
+    work: impl FnOnce() -> T,
+) -> T {
+    let progress = FetchProgress {
+        request_id,
+        kind,
+        phase: FetchPhase::Searching,
+        pages_fetched: 0,
+        items_fetched: 0,
+    };
+    emit(&progress);
+    CURRENT.with(|current| {
+        *current.borrow_mut() = Some(Tracker {
+            progress,
+            emit: Box::new(emit),
+        })
+    });
+
+    let result = work();
+
+    if let Some(mut tracker) = CURRENT.with(|current| current.borrow_mut().take()) {
+        tracker.progress.phase = FetchPhase::Done;
+        (tracker.emit)(&tracker.progress);
+    }
+    result
+}
+
+fn update(change: impl FnOnce(&mut FetchProgress)) {
+    CURRENT.with(|current| {
+        if let Some(tracker) = current.borrow_mut().as_mut() {
+            change(&mut tracker.progress);
+            (tracker.emit)(&tracker.progress);
+        }
+    });
+}
+
+/// Records a page of `items` search results.
+pub fn page_fetched(items: usize) {
+    update(|progress| {
+        progress.phase = FetchPhase::Searching;
+        progress.pages_fetched += 1;
+        progress.items_fetched += items;
+    });
+}
+
+pub fn set_phase(phase: FetchPhase) {
+    update(|progress| progress.phase = phase);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn test_track_reports_pages_and_phases() {
+        let updates = Rc::new(RefCell::new(Vec::new()));
+        let recorded = updates.clone();
+
+        let result = track(
+            "authored",
+            Some("req-1".to_string()),
+            move |progress| recorded.borrow_mut().push(progress.clone()),
+            || {
+                page_fetched(100);
+                page_fetched(40);
+                set_phase(FetchPhase::Enriching);
+                "done"
+            },
+        );
+
+        let updates = updates.borrow();
+        let summary: Vec<(FetchPhase, u32, usize)> = updates
+            .iter()
+            .map(|p| (p.phase, p.pages_fetched, p.items_fetched))
+            .collect();
+        assert_eq!(result, "done");
+        assert_eq!(
+            summary,
+            vec![
+                (FetchPhase::Searching, 0, 0),
+                (FetchPhase::Searching, 1, 100),
+                (FetchPhase::Searching, 2, 140),
+                (FetchPhase::Enriching, 2, 140),
+                (FetchPhase::Done, 2, 140),
+            ]
+        );
+        assert_eq!(updates[0].request_id.as_deref(), Some("req-1"));
+    }
+
+    #[test]
+    fn test_updates_without_tracking_are_ignored() {
+        page_fetched(10);
+        set_phase(FetchPhase::Done);
+    }
+
+    #[test]
+    fn test_progress_serialization() {
+        let progress = FetchProgress {
+            request_id: None,
+            kind: "reviewed",
+            phase: FetchPhase::Enriching,
+            pages_fetched: 3,
+            items_fetched: 250,
+        };
+
+        assert_eq!(
+            serde_json::to_string(&progress).unwrap(),
+            r#"{"requestId":null,"kind":"reviewed","phase":"enriching","pagesFetched":3,"itemsFetched":250}"#
+        );
+    }
+}
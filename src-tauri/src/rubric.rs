@@ -0,0 +1,368 @@
+use crate::PullRequest;
+use crate::error::{AppError, ErrorCode};
+use crate::language::Language;
+use crate::llm;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::Path;
+
+/// PRs matched to competencies per request, keeping each reply well within `MAPPING_MAX_TOKENS`.
+pub const MAPPING_BATCH_SIZE: usize = 40;
+pub const MAPPING_MAX_TOKENS: u32 = 3000;
+
+/// A leveled engineering ladder, loaded from YAML or JSON.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Rubric {
+    #[serde(default)]
+    pub name: Option<String>,
+    pub competencies: Vec<Competency>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Competency {
+    /// Short identifier the model refers to the competency by. Defaults to `name`.
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    /// What is expected at each level, e.g. `{"L4": "...", "L5": "..."}`.
+    #[serde(default)]
+    pub levels: BTreeMap<String, String>,
+}
+
+impl Competency {
+    pub fn id(&self) -> &str {
+        self.id.as_deref().unwrap_or(&self.name)
+    }
+}
+
+/// A PR that demonstrates a competency.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Evidence {
+    pub title: String,
+    pub url: String,
+    #[serde(rename = "nameWithOwner")]
+    pub name_with_owner: String,
+    /// What the PR shows about the competency.
+    pub note: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CompetencyEvidence {
+    #[serde(rename = "competencyId")]
+    pub competency_id: String,
+    pub competency: String,
+    pub evidence: Vec<Evidence>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MappingResponse {
+    mappings: Vec<Mapping>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Mapping {
+    /// 1-based number of the PR in the batch.
+    pr: usize,
+    competencies: Vec<String>,
+    #[serde(default)]
+    evidence: String,
+}
+
+/// Reads a rubric from `path`: JSON for `.json` files, YAML otherwise.
+pub fn load(path: &Path) -> Result<Rubric, AppError> {
+    let content = fs::read_to_string(path).map_err(|e| {
+        AppError::new(
+            ErrorCode::Config,
+            format!("Failed to read rubric {}: {}", path.display(), e),
+        )
+    })?;
+
+    let rubric: Rubric = if path.extension().is_some_and(|ext| ext == "json") {
+        serde_json::from_str(&content).map_err(|e| e.to_string())
+    } else {
+        serde_yaml::from_str(&content).map_err(|e| e.to_string())
+    }
+    .map_err(|e| {
+        AppError::new(
+            ErrorCode::Parse,
+            format!("Failed to parse rubric {}: {}", path.display(), e),
+        )
+    })?;
+
+    if rubric.competencies.is_empty() {
+        return Err(AppError::new(
+            ErrorCode::Config,
+            "The rubric has no competencies",
+        ));
+    }
+    let mut ids = HashSet::new();
+    if let Some(duplicate) = rubric.competencies.iter().find(|c| !ids.insert(c.id())) {
+        return Err(AppError::new(
+            ErrorCode::Config,
+            format!("The rubric lists competency {:?} twice", duplicate.id()),
+        ));
+    }
+
+    Ok(rubric)
+}
+
+/// The competencies and, for `level` or every level, what is expected.
+pub fn describe(rubric: &Rubric, level: Option<&str>) -> String {
+    rubric
+        .competencies
+        .iter()
+        .map(|competency| {
+            let mut line = format!("- `{}`: {}", competency.id(), competency.name);
+            if let Some(description) = &competency.description {
+                line.push_str(&format!(" — {}", description));
+            }
+            for (name, expectation) in &competency.levels {
+                if level.is_none_or(|level| level == name) {
+                    line.push_str(&format!("\n  {}: {}", name, expectation));
+                }
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prompt section framing the summary around the ladder, for the `rubric_section` placeholder.
+pub fn prompt_section(rubric: &Rubric, level: Option<&str>) -> String {
+    let target = level
+        .map(|level| format!(", with the expectations for {}", level))
+        .unwrap_or_default();
+    format!(
+        "\n\nThe engineer is assessed against the {} career ladder. These are its competencies{}:\n\n{}",
+        rubric.name.as_deref().unwrap_or("team's"),
+        target,
+        describe(rubric, level)
+    )
+}
+
+/// Prompt asking which competencies each of `prs` gives evidence for, as JSON.
+pub fn mapping_prompt(
+    rubric: &Rubric,
+    level: Option<&str>,
+    language: Language,
+    prs: &[PullRequest],
+) -> String {
+    let pr_list: Vec<String> = prs
+        .iter()
+        .enumerate()
+        .map(|(i, pr)| {
+            let body: String = pr
+                .body
+                .as_deref()
+                .unwrap_or_default()
+                .chars()
+                .take(200)
+                .collect();
+            format!(
+                "{}. {} ({})\n   {}",
+                i + 1,
+                pr.title,
+                pr.repository.name_with_owner,
+                body.replace('\n', " ")
+            )
+        })
+        .collect();
+
+    format!(
+        r#"You map an engineer's merged pull requests to the competencies of their career ladder.
+
+Competencies:
+
+{}
+
+For each pull request below, list the ids of the competencies it gives real evidence for, with one factual sentence in {} on what it shows. Leave out pull requests that show none. Only use the competency ids above, and never invent outcomes or metrics.
+
+Reply with JSON only, in this shape:
+{{"mappings": [{{"pr": 1, "competencies": ["id"], "evidence": "One sentence."}}]}}
+
+Pull requests:
+
+{}"#,
+        describe(rubric, level),
+        language.name(),
+        pr_list.join("\n")
+    )
+}
+
+/// Reads the model's reply to [`mapping_prompt`] for `prs` into `evidence`,
+/// skipping PR numbers and competency ids that don't exist.
+pub fn add_mappings(
+    evidence: &mut [CompetencyEvidence],
+    prs: &[PullRequest],
+    reply: &str,
+) -> Result<(), AppError> {
+    let response: MappingResponse = serde_json::from_str(llm::json_body(reply)).map_err(|e| {
+        AppError::new(
+            ErrorCode::Parse,
+            format!("Failed to parse rubric mapping: {}", e),
+        )
+    })?;
+
+    for mapping in response.mappings {
+        let Some(pr) = mapping.pr.checked_sub(1).and_then(|i| prs.get(i)) else {
+            continue;
+        };
+        for id in &mapping.competencies {
+            let Some(entry) = evidence.iter_mut().find(|e| &e.competency_id == id) else {
+                continue;
+            };
+            if entry.evidence.iter().any(|e| e.url == pr.url) {
+                continue;
+            }
+            entry.evidence.push(Evidence {
+                title: pr.title.clone(),
+                url: pr.url.clone(),
+                name_with_owner: pr.repository.name_with_owner.clone(),
+                note: mapping.evidence.clone(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// One empty entry per competency, in rubric order, for [`add_mappings`] to fill.
+pub fn empty_evidence(rubric: &Rubric) -> Vec<CompetencyEvidence> {
+    rubric
+        .competencies
+        .iter()
+        .map(|competency| CompetencyEvidence {
+            competency_id: competency.id().to_string(),
+            competency: competency.name.clone(),
+            evidence: Vec::new(),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Repository;
+
+    fn rubric() -> Rubric {
+        serde_yaml::from_str(
+            r#"
+name: Acme Engineering Ladder
+competencies:
+  - id: craft
+    name: Craft
+    description: Quality of technical work
+    levels:
+      L4: Ships well-tested features independently
+      L5: Sets technical direction for a team
+  - name: Delivery
+"#,
+        )
+        .unwrap()
+    }
+
+    fn pr(number: i32) -> PullRequest {
+        PullRequest {
+            title: format!("PR {}", number),
+            url: format!("https://github.com/acme/web/pull/{}", number),
+            body: Some("Adds retries\nto the client".to_string()),
+            closed_at: "2024-01-05".to_string(),
+            created_at: None,
+            number: Some(number),
+            repository: Repository {
+                name: "web".to_string(),
+                name_with_owner: "acme/web".to_string(),
+                host: None,
+            },
+            size: None,
+            labels: Vec::new(),
+            milestone: None,
+            closing_issues: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_load_json_and_yaml() {
+        let dir = std::env::temp_dir().join(format!("rubric-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let json = dir.join("ladder.json");
+        let yaml = dir.join("ladder.yaml");
+        let duplicate = dir.join("duplicate.yml");
+        fs::write(&json, r#"{"competencies": [{"name": "Craft"}]}"#).unwrap();
+        fs::write(&yaml, serde_yaml::to_string(&rubric()).unwrap()).unwrap();
+        fs::write(
+            &duplicate,
+            "competencies:\n  - name: Craft\n  - id: Craft\n    name: Other\n",
+        )
+        .unwrap();
+
+        let from_json = load(&json);
+        let from_yaml = load(&yaml);
+        let duplicated = load(&duplicate);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(from_json.unwrap().competencies[0].id(), "Craft");
+        assert_eq!(from_yaml.unwrap(), rubric());
+        assert_eq!(duplicated.unwrap_err().code, ErrorCode::Config);
+        assert_eq!(
+            load(Path::new("/nonexistent/ladder.yaml"))
+                .unwrap_err()
+                .code,
+            ErrorCode::Config
+        );
+    }
+
+    #[test]
+    fn test_describe_filters_levels() {
+        let described = describe(&rubric(), Some("L5"));
+
+        assert_eq!(
+            described,
+            "- `craft`: Craft — Quality of technical work\n  L5: Sets technical direction for a team\n- `Delivery`: Delivery"
+        );
+    }
+
+    #[test]
+    fn test_add_mappings_skips_unknown_prs_and_competencies() {
+        let rubric = rubric();
+        let prs = vec![pr(1), pr(2)];
+        let mut evidence = empty_evidence(&rubric);
+
+        add_mappings(
+            &mut evidence,
+            &prs,
+            r#"```json
+{"mappings": [
+  {"pr": 1, "competencies": ["craft", "Delivery", "influence"], "evidence": "Added retries."},
+  {"pr": 1, "competencies": ["craft"], "evidence": "Duplicate."},
+  {"pr": 7, "competencies": ["craft"], "evidence": "Out of range."}
+]}
+```"#,
+        )
+        .unwrap();
+
+        assert_eq!(evidence[0].competency_id, "craft");
+        assert_eq!(evidence[0].evidence.len(), 1);
+        assert_eq!(evidence[0].evidence[0].note, "Added retries.");
+        assert_eq!(evidence[1].evidence[0].url, prs[0].url);
+        assert_eq!(
+            add_mappings(&mut evidence, &prs, "no json")
+                .unwrap_err()
+                .code,
+            ErrorCode::Parse
+        );
+    }
+
+    #[test]
+    fn test_mapping_prompt_numbers_prs() {
+        let prompt = mapping_prompt(&rubric(), None, Language::PtBr, &[pr(10), pr(11)]);
+
+        assert!(prompt.contains("1. PR 10 (acme/web)\n   Adds retries to the client"));
+        assert!(prompt.contains("2. PR 11 (acme/web)"));
+        assert!(prompt.contains("L4: Ships well-tested features independently"));
+        assert!(prompt.contains("one factual sentence in Brazilian Portuguese (pt-BR)"));
+    }
+}
@@ -0,0 +1,165 @@
+use crate::cancel;
+use crate::error::AppError;
+use chrono::{Duration, NaiveDate};
+
+/// GitHub search never returns more than this many results for a single query,
+/// no matter how many pages are requested.
+pub const SEARCH_RESULT_CAP: usize = 1000;
+
+#[derive(Debug)]
+pub struct SearchOutcome<T> {
+    pub items: Vec<T>,
+    pub complete: bool,
+}
+
+fn parse_date(date: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+fn format_range(start: NaiveDate, end: NaiveDate) -> String {
+    format!("{}..{}", start.format("%Y-%m-%d"), end.format("%Y-%m-%d"))
+}
+
+/// Splits an inclusive date range into two non-overlapping halves.
+/// Returns `None` when the range is a single day and cannot be split further.
+fn split_range(start: NaiveDate, end: NaiveDate) -> Option<[(NaiveDate, NaiveDate); 2]> {
+    if start >= end {
+        return None;
+    }
+
+    let mid = start + Duration::days((end - start).num_days() / 2);
+    Some([(start, mid), (mid + Duration::days(1), end)])
+}
+
+/// Runs `fetch` over the `start_date..end_date` range, splitting the range into
+/// smaller windows whenever a window hits the search result cap.
+///
+/// `fetch` receives a `YYYY-MM-DD..YYYY-MM-DD` range and should return every
+/// result GitHub is willing to give for it (up to [`SEARCH_RESULT_CAP`]).
+/// The outcome is marked incomplete when a single day still exceeds the cap.
+pub fn search_in_windows<T, F>(
+    start_date: &str,
+    end_date: &str,
+    mut fetch: F,
+) -> Result<SearchOutcome<T>, AppError>
+where
+    F: FnMut(&str) -> Result<Vec<T>, AppError>,
+{
+    let (start, end) = match (parse_date(start_date), parse_date(end_date)) {
+        (Some(start), Some(end)) => (start, end),
+        _ => {
+            let items = fetch(&format!("{}..{}", start_date, end_date))?;
+            let complete = items.len() < SEARCH_RESULT_CAP;
+            return Ok(SearchOutcome { items, complete });
+        }
+    };
+
+    let mut items = Vec::new();
+    let mut complete = true;
+    let mut pending = vec![(start, end)];
+
+    while let Some((window_start, window_end)) = pending.pop() {
+        cancel::check()?;
+        let batch = fetch(&format_range(window_start, window_end))?;
+
+        if batch.len() < SEARCH_RESULT_CAP {
+            items.extend(batch);
+            continue;
+        }
+
+        match split_range(window_start, window_end) {
+            Some([first, second]) => {
+                pending.push(second);
+                pending.push(first);
+            }
+            None => {
+                items.extend(batch);
+                complete = false;
+            }
+        }
+    }
+
+    Ok(SearchOutcome { items, complete })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+
+    fn day(date: &str) -> NaiveDate {
+        parse_date(date).unwrap()
+    }
+
+    #[test]
+    fn test_split_range_halves() {
+        let [first, second] = split_range(day("2024-01-01"), day("2024-01-10")).unwrap();
+        assert_eq!(first, (day("2024-01-01"), day("2024-01-05")));
+        assert_eq!(second, (day("2024-01-06"), day("2024-01-10")));
+    }
+
+    #[test]
+    fn test_split_range_single_day() {
+        assert!(split_range(day("2024-01-01"), day("2024-01-01")).is_none());
+    }
+
+    #[test]
+    fn test_search_in_windows_single_window() {
+        let mut calls = Vec::new();
+        let outcome = search_in_windows("2024-01-01", "2024-12-31", |range| {
+            calls.push(range.to_string());
+            Ok(vec![1, 2, 3])
+        })
+        .unwrap();
+
+        assert_eq!(calls, vec!["2024-01-01..2024-12-31"]);
+        assert_eq!(outcome.items.len(), 3);
+        assert!(outcome.complete);
+    }
+
+    #[test]
+    fn test_search_in_windows_splits_capped_window() {
+        let mut calls = Vec::new();
+        let outcome = search_in_windows("2024-01-01", "2024-01-04", |range| {
+            calls.push(range.to_string());
+            if range == "2024-01-01..2024-01-04" {
+                Ok(vec![0; SEARCH_RESULT_CAP])
+            } else {
+                Ok(vec![0; 10])
+            }
+        })
+        .unwrap();
+
+        assert_eq!(
+            calls,
+            vec![
+                "2024-01-01..2024-01-04",
+                "2024-01-01..2024-01-02",
+                "2024-01-03..2024-01-04"
+            ]
+        );
+        assert_eq!(outcome.items.len(), 20);
+        assert!(outcome.complete);
+    }
+
+    #[test]
+    fn test_search_in_windows_marks_capped_day_incomplete() {
+        let outcome = search_in_windows("2024-01-01", "2024-01-01", |_| {
+            Ok(vec![0; SEARCH_RESULT_CAP])
+        })
+        .unwrap();
+
+        assert_eq!(outcome.items.len(), SEARCH_RESULT_CAP);
+        assert!(!outcome.complete);
+    }
+
+    #[test]
+    fn test_search_in_windows_propagates_errors() {
+        let result: Result<SearchOutcome<i32>, AppError> =
+            search_in_windows("2024-01-01", "2024-01-31", |_| {
+                Err(AppError::new(ErrorCode::Network, "boom"))
+            });
+
+        assert_eq!(result.unwrap_err().message, "boom");
+    }
+}
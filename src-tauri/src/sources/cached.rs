@@ -0,0 +1,413 @@
+use super::ActivitySource;
+use crate::cache::{ActivityCache, Cacheable};
+use crate::error::AppError;
+use crate::search::SearchOutcome;
+use crate::{Issue, IssueInvolvement, PullRequest, ReviewedPullRequest};
+use chrono::{NaiveDate, Utc};
+use std::sync::{Arc, Mutex, OnceLock};
+
+/// Serves pull requests from the local activity cache, fetching only the days
+/// that haven't been synced yet from the wrapped source.
+///
+/// Pull requests are cached for every owner and filtered locally, so switching
+/// organizations doesn't trigger another fetch. When fetching fails (no network,
+/// `gh` missing) whatever is cached is served instead and [`ActivitySource::as_of`]
+/// reports when it was last synced. Issues aren't cached: they are always fetched
+/// from the wrapped source, so they are unavailable offline.
+///
+/// Records are kept per account (`login@host`), so two accounts on the same host
+/// never see each other's activity.
+pub struct CachedSource {
+    inner: Box<dyn ActivitySource>,
+    cache: Arc<ActivityCache>,
+    host: String,
+    scope: OnceLock<String>,
+    today: NaiveDate,
+    as_of: Mutex<Option<String>>,
+}
+
+impl CachedSource {
+    pub fn new(inner: Box<dyn ActivitySource>, cache: Arc<ActivityCache>, host: &str) -> Self {
+        Self {
+            inner,
+            cache,
+            host: host.to_string(),
+            scope: OnceLock::new(),
+            // Search date qualifiers are evaluated in UTC, so "today" is too.
+            today: Utc::now().date_naive(),
+            as_of: Mutex::new(None),
+        }
+    }
+
+    /// The cache scope, `login@host`. When the login can't be looked up (offline),
+    /// the last login seen on this host is used so its cached records can still be served.
+    fn scope(&self) -> Result<&str, AppError> {
+        if let Some(scope) = self.scope.get() {
+            return Ok(scope);
+        }
+
+        let login = match self.inner.login() {
+            Ok(Some(login)) => {
+                self.cache.set_last_login(&self.host, &login)?;
+                login
+            }
+            Ok(None) => return Ok(self.scope.get_or_init(|| self.host.clone())),
+            Err(error) => self.cache.last_login(&self.host)?.ok_or(error)?,
+        };
+        Ok(self
+            .scope
+            .get_or_init(|| format!("{}@{}", login, self.host)))
+    }
+
+    /// Falls back to cached records when `result` is a fetch error, as long as
+    /// this scope has been synced at least once. The fallback is only complete
+    /// when every day of the range has been synced.
+    fn or_cached<T: Cacheable>(
+        &self,
+        result: Result<SearchOutcome<T>, AppError>,
+        start: &str,
+        end: &str,
+        owner: Option<&str>,
+    ) -> Result<SearchOutcome<T>, AppError> {
+        let error = match result {
+            Ok(outcome) => return Ok(outcome),
+            Err(error) => error,
+        };
+        let scope = self.scope()?;
+        let Some(synced_at) = self.cache.last_synced_at(scope, T::KIND)? else {
+            return Err(error);
+        };
+
+        let mut as_of = self.as_of.lock().unwrap_or_else(|e| e.into_inner());
+        if as_of.as_ref().is_none_or(|current| synced_at < *current) {
+            *as_of = Some(synced_at);
+        }
+        drop(as_of);
+
+        Ok(SearchOutcome {
+            items: self.cache.load(scope, start, end, owner)?,
+            complete: self.cache.covers(scope, T::KIND, start, end)?,
+        })
+    }
+}
+
+fn split_range(date_range: &str) -> (&str, &str) {
+    date_range
+        .split_once("..")
+        .unwrap_or((date_range, date_range))
+}
+
+impl ActivitySource for CachedSource {
+    fn authored_prs(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        let (start, end) = split_range(date_range);
+        self.search_authored(start, end, owner)
+            .map(|outcome| outcome.items)
+    }
+
+    fn reviewed_prs(&self, date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+        let (start, end) = split_range(date_range);
+        self.search_reviewed(start, end)
+            .map(|outcome| outcome.items)
+    }
+
+    fn issues(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+        involvement: IssueInvolvement,
+    ) -> Result<Vec<Issue>, AppError> {
+        self.inner.issues(date_range, owner, involvement)
+    }
+
+    fn as_of(&self) -> Option<String> {
+        self.as_of.lock().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+
+    fn search_authored(
+        &self,
+        start: &str,
+        end: &str,
+        owner: Option<&str>,
+    ) -> Result<SearchOutcome<PullRequest>, AppError> {
+        let result = self.scope().and_then(|scope| {
+            self.cache
+                .sync(scope, start, end, self.today, owner, |start, end| {
+                    self.inner.search_authored(start, end, None)
+                })
+        });
+        self.or_cached(result, start, end, owner)
+    }
+
+    fn search_reviewed(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> Result<SearchOutcome<ReviewedPullRequest>, AppError> {
+        let result = self.scope().and_then(|scope| {
+            self.cache
+                .sync(scope, start, end, self.today, None, |start, end| {
+                    self.inner.search_reviewed(start, end)
+                })
+        });
+        self.or_cached(result, start, end, None)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Repository;
+    use crate::error::ErrorCode;
+    use crate::sources::UnavailableSource;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct CountingSource {
+        login: &'static str,
+        calls: Rc<RefCell<Vec<String>>>,
+        offline: Rc<RefCell<bool>>,
+    }
+
+    impl ActivitySource for CountingSource {
+        fn authored_prs(
+            &self,
+            date_range: &str,
+            _owner: Option<&str>,
+        ) -> Result<Vec<PullRequest>, AppError> {
+            self.calls.borrow_mut().push(date_range.to_string());
+            if *self.offline.borrow() {
+                return Err(AppError::gh_not_installed());
+            }
+            let (start, _) = split_range(date_range);
+
+            Ok(vec![pr(start)])
+        }
+
+        fn reviewed_prs(&self, _date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+            Ok(Vec::new())
+        }
+
+        fn login(&self) -> Result<Option<String>, AppError> {
+            if *self.offline.borrow() {
+                return Err(AppError::gh_not_installed());
+            }
+            Ok(Some(self.login.to_string()))
+        }
+    }
+
+    fn pr(day: &str) -> PullRequest {
+        PullRequest {
+            title: format!("Change from {}", day),
+            url: format!("https://github.com/org/repo/pull/{}", day),
+            body: None,
+            closed_at: format!("{}T10:00:00Z", day),
+            created_at: None,
+            number: None,
+            repository: Repository {
+                name: "repo".to_string(),
+                name_with_owner: "org/repo".to_string(),
+                host: None,
+            },
+            size: None,
+            labels: Vec::new(),
+            milestone: None,
+            closing_issues: Vec::new(),
+        }
+    }
+
+    fn cached_source(cache: Arc<ActivityCache>, calls: Rc<RefCell<Vec<String>>>) -> CachedSource {
+        let offline = Rc::new(RefCell::new(false));
+        cached_source_with(cache, calls, offline)
+    }
+
+    fn cached_source_with(
+        cache: Arc<ActivityCache>,
+        calls: Rc<RefCell<Vec<String>>>,
+        offline: Rc<RefCell<bool>>,
+    ) -> CachedSource {
+        account_source(cache, "octocat", calls, offline)
+    }
+
+    fn account_source(
+        cache: Arc<ActivityCache>,
+        login: &'static str,
+        calls: Rc<RefCell<Vec<String>>>,
+        offline: Rc<RefCell<bool>>,
+    ) -> CachedSource {
+        let mut source = CachedSource::new(
+            Box::new(CountingSource {
+                login,
+                calls,
+                offline,
+            }),
+            cache,
+            "github.com",
+        );
+        source.today = NaiveDate::from_ymd_opt(2024, 6, 15).unwrap();
+        source
+    }
+
+    #[test]
+    fn test_only_fetches_days_outside_synced_range() {
+        let cache = Arc::new(ActivityCache::open_in_memory().unwrap());
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let source = cached_source(cache, calls.clone());
+
+        let first = source.authored_prs("2024-01-01..2024-03-31", None).unwrap();
+        let second = source.authored_prs("2024-02-01..2024-04-30", None).unwrap();
+        let by_owner = source
+            .authored_prs("2024-01-15..2024-04-15", Some("ORG"))
+            .unwrap();
+        let other_owner = source
+            .authored_prs("2024-01-01..2024-04-30", Some("other"))
+            .unwrap();
+
+        assert_eq!(first.len(), 1);
+        assert_eq!(second.len(), 1);
+        assert_eq!(by_owner[0].title, "Change from 2024-04-01");
+        assert!(other_owner.is_empty());
+        assert_eq!(
+            *calls.borrow(),
+            vec!["2024-01-01..2024-03-31", "2024-04-01..2024-04-30"]
+        );
+    }
+
+    #[test]
+    fn test_never_marks_today_as_synced() {
+        let cache = Arc::new(ActivityCache::open_in_memory().unwrap());
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let source = cached_source(cache, calls.clone());
+
+        source.authored_prs("2024-06-01..2024-06-15", None).unwrap();
+        source.authored_prs("2024-06-01..2024-06-15", None).unwrap();
+
+        assert_eq!(
+            *calls.borrow(),
+            vec!["2024-06-01..2024-06-15", "2024-06-15..2024-06-15"]
+        );
+    }
+
+    #[test]
+    fn test_serves_cached_results_when_offline() {
+        let cache = Arc::new(ActivityCache::open_in_memory().unwrap());
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let offline = Rc::new(RefCell::new(false));
+        let source = cached_source_with(cache, calls, offline.clone());
+
+        source.authored_prs("2024-06-01..2024-06-15", None).unwrap();
+        assert!(source.as_of().is_none());
+
+        *offline.borrow_mut() = true;
+        let prs = source.authored_prs("2024-06-01..2024-06-15", None).unwrap();
+
+        assert_eq!(prs[0].title, "Change from 2024-06-01");
+        assert!(source.as_of().is_some());
+    }
+
+    #[test]
+    fn test_partially_synced_ranges_are_incomplete_when_offline() {
+        let cache = Arc::new(ActivityCache::open_in_memory().unwrap());
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let offline = Rc::new(RefCell::new(false));
+        let source = cached_source_with(cache, calls, offline.clone());
+
+        source.authored_prs("2024-06-01..2024-06-10", None).unwrap();
+
+        *offline.borrow_mut() = true;
+        let partial = source
+            .search_authored("2024-05-01", "2024-06-10", None)
+            .unwrap();
+        let synced = source
+            .search_authored("2024-06-01", "2024-06-10", None)
+            .unwrap();
+
+        assert_eq!(partial.items[0].title, "Change from 2024-06-01");
+        assert!(!partial.complete);
+        assert!(synced.complete);
+    }
+
+    #[test]
+    fn test_capped_searches_are_incomplete_and_not_marked_synced() {
+        let cache = Arc::new(ActivityCache::open_in_memory().unwrap());
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let source = cached_source(cache, calls.clone());
+        let capped = |start: &str, _: &str| {
+            Ok(SearchOutcome {
+                items: vec![pr(start)],
+                complete: false,
+            })
+        };
+
+        let outcome = source
+            .cache
+            .sync(
+                source.scope().unwrap(),
+                "2024-01-01",
+                "2024-01-31",
+                source.today,
+                None,
+                capped,
+            )
+            .unwrap();
+        let again = source
+            .search_authored("2024-01-01", "2024-01-31", None)
+            .unwrap();
+
+        assert!(!outcome.complete);
+        assert_eq!(outcome.items.len(), 1);
+        assert!(again.complete);
+        assert_eq!(*calls.borrow(), vec!["2024-01-01..2024-01-31"]);
+    }
+
+    #[test]
+    fn test_reports_error_when_offline_without_cache() {
+        let cache = Arc::new(ActivityCache::open_in_memory().unwrap());
+        let offline = Rc::new(RefCell::new(true));
+        let source = cached_source_with(cache, Rc::new(RefCell::new(Vec::new())), offline);
+
+        let result = source.authored_prs("2024-06-01..2024-06-15", None);
+
+        assert_eq!(result.unwrap_err().code, ErrorCode::GhNotInstalled);
+    }
+
+    #[test]
+    fn test_keeps_accounts_on_the_same_host_apart() {
+        let cache = Arc::new(ActivityCache::open_in_memory().unwrap());
+        let calls = Rc::new(RefCell::new(Vec::new()));
+        let online = || Rc::new(RefCell::new(false));
+        let first = account_source(cache.clone(), "octocat", calls.clone(), online());
+        let second = account_source(cache.clone(), "hubot", calls.clone(), online());
+
+        first.authored_prs("2024-01-01..2024-01-31", None).unwrap();
+        second.authored_prs("2024-01-01..2024-01-31", None).unwrap();
+
+        assert_eq!(first.scope().unwrap(), "octocat@github.com");
+        assert_eq!(second.scope().unwrap(), "hubot@github.com");
+        assert_eq!(calls.borrow().len(), 2);
+        assert_eq!(
+            cache.last_login("github.com").unwrap().as_deref(),
+            Some("hubot")
+        );
+    }
+
+    #[test]
+    fn test_serves_cached_results_for_unavailable_hosts() {
+        let cache = Arc::new(ActivityCache::open_in_memory().unwrap());
+        let online = cached_source(cache.clone(), Rc::new(RefCell::new(Vec::new())));
+        online.authored_prs("2024-06-01..2024-06-15", None).unwrap();
+
+        let error = AppError::new(ErrorCode::NotAuthenticated, "No GitHub token configured");
+        let offline =
+            CachedSource::new(Box::new(UnavailableSource::new(error)), cache, "github.com");
+        let prs = offline
+            .authored_prs("2024-06-01..2024-06-15", None)
+            .unwrap();
+
+        assert_eq!(prs[0].title, "Change from 2024-06-01");
+        assert!(offline.as_of().is_some());
+    }
+}
@@ -0,0 +1,153 @@
+use super::{ActivitySource, owned_by};
+use crate::error::{AppError, ErrorCode};
+use crate::{Issue, IssueInvolvement, PullRequest, ReviewedPullRequest};
+use serde::de::DeserializeOwned;
+use std::fs;
+use std::path::PathBuf;
+
+/// Serves activity from JSON files in a local directory, in the same shape `gh`
+/// returns: `authored.json` holds `PullRequest`s and `reviewed.json` holds
+/// `ReviewedPullRequest`s and `issues.json` holds `Issue`s tagged with their
+/// `involvement`. Useful for demos and for testing without network access.
+pub struct FixtureSource {
+    dir: PathBuf,
+}
+
+impl FixtureSource {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    fn load<T: DeserializeOwned>(&self, file_name: &str) -> Result<Vec<T>, AppError> {
+        let path = self.dir.join(file_name);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&path).map_err(|e| {
+            AppError::new(
+                ErrorCode::Io,
+                format!("Failed to read {}: {}", path.display(), e),
+            )
+        })?;
+        serde_json::from_str(&content).map_err(|e| {
+            AppError::new(
+                ErrorCode::Parse,
+                format!("Failed to parse {}: {}", path.display(), e),
+            )
+        })
+    }
+}
+
+/// Checks whether an ISO 8601 timestamp falls on a day inside `start..end`.
+fn in_date_range(timestamp: &str, date_range: &str) -> bool {
+    let Some((start, end)) = date_range.split_once("..") else {
+        return true;
+    };
+    let day = timestamp.get(..10).unwrap_or(timestamp);
+    day >= start && day <= end
+}
+
+impl ActivitySource for FixtureSource {
+    fn authored_prs(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        let prs: Vec<PullRequest> = self.load("authored.json")?;
+
+        Ok(prs
+            .into_iter()
+            .filter(|pr| in_date_range(&pr.closed_at, date_range))
+            .filter(|pr| owned_by(&pr.repository, owner))
+            .collect())
+    }
+
+    fn reviewed_prs(&self, date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+        let prs: Vec<ReviewedPullRequest> = self.load("reviewed.json")?;
+
+        Ok(prs
+            .into_iter()
+            .filter(|pr| {
+                pr.closed_at
+                    .as_deref()
+                    .is_some_and(|closed_at| in_date_range(closed_at, date_range))
+            })
+            .collect())
+    }
+
+    fn issues(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+        involvement: IssueInvolvement,
+    ) -> Result<Vec<Issue>, AppError> {
+        let issues: Vec<Issue> = self.load("issues.json")?;
+
+        Ok(issues
+            .into_iter()
+            .filter(|issue| issue.involvement == involvement)
+            .filter(|issue| {
+                let date = match involvement {
+                    IssueInvolvement::Closed => issue.closed_at.as_deref(),
+                    _ => Some(issue.created_at.as_str()),
+                };
+                date.is_some_and(|date| in_date_range(date, date_range))
+            })
+            .filter(|issue| owned_by(&issue.repository, owner))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_in_date_range() {
+        assert!(in_date_range(
+            "2024-03-01T10:00:00Z",
+            "2024-03-01..2024-03-31"
+        ));
+        assert!(in_date_range(
+            "2024-03-31T23:59:59Z",
+            "2024-03-01..2024-03-31"
+        ));
+        assert!(!in_date_range(
+            "2024-04-01T00:00:00Z",
+            "2024-03-01..2024-03-31"
+        ));
+    }
+
+    #[test]
+    fn test_fixture_source_filters_by_range_and_owner() {
+        let dir = std::env::temp_dir().join(format!("myimpact-fixture-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("authored.json"),
+            r#"[
+                {"title": "A", "url": "u1", "body": null, "closedAt": "2024-03-05T00:00:00Z",
+                 "repository": {"name": "repo", "nameWithOwner": "acme/repo"}},
+                {"title": "B", "url": "u2", "body": null, "closedAt": "2024-03-06T00:00:00Z",
+                 "repository": {"name": "tool", "nameWithOwner": "other/tool"}},
+                {"title": "C", "url": "u3", "body": null, "closedAt": "2024-05-01T00:00:00Z",
+                 "repository": {"name": "repo", "nameWithOwner": "acme/repo"}}
+            ]"#,
+        )
+        .unwrap();
+
+        let source = FixtureSource::new(&dir);
+        let all = source.authored_prs("2024-03-01..2024-03-31", None).unwrap();
+        let acme = source
+            .authored_prs("2024-03-01..2024-03-31", Some("acme"))
+            .unwrap();
+        let reviewed = source.reviewed_prs("2024-03-01..2024-03-31").unwrap();
+
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(all.len(), 2);
+        assert_eq!(acme.len(), 1);
+        assert_eq!(acme[0].title, "A");
+        assert!(reviewed.is_empty());
+    }
+}
@@ -0,0 +1,282 @@
+use super::{ActivitySource, pr_details, pr_lookup, review_depth};
+use crate::cancel;
+use crate::error::{AppError, ErrorCode};
+use crate::progress;
+use crate::search::{SEARCH_RESULT_CAP, SearchOutcome, search_in_windows};
+use crate::{Issue, IssueInvolvement, PullRequest, ReviewedPullRequest, gh_command};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+#[derive(Debug, Deserialize)]
+struct SimplePr {
+    repository: SimpleRepo,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimpleRepo {
+    #[serde(rename = "nameWithOwner")]
+    name_with_owner: String,
+}
+
+fn search_prs_args(role: &str, date_range: &str, fields: &str) -> Vec<String> {
+    vec![
+        "search".to_string(),
+        "prs".to_string(),
+        format!("--{}", role),
+        "@me".to_string(),
+        "--merged-at".to_string(),
+        date_range.to_string(),
+        "--json".to_string(),
+        fields.to_string(),
+        "--limit".to_string(),
+        SEARCH_RESULT_CAP.to_string(),
+    ]
+}
+
+fn search_issues_args(
+    date_range: &str,
+    owner: Option<&str>,
+    involvement: IssueInvolvement,
+) -> Vec<String> {
+    let qualifiers: &[&str] = match involvement {
+        IssueInvolvement::Opened => &["--author", "@me", "--created"],
+        IssueInvolvement::Closed => &["--assignee", "@me", "--state", "closed", "--closed"],
+        IssueInvolvement::Commented => &["--commenter", "@me", "--updated"],
+    };
+
+    let mut args: Vec<String> = ["search", "issues"]
+        .iter()
+        .chain(qualifiers)
+        .map(|s| s.to_string())
+        .collect();
+    args.push(date_range.to_string());
+
+    if let Some(org) = owner {
+        args.push("--owner".to_string());
+        args.push(org.to_string());
+    }
+
+    args.extend([
+        "--json".to_string(),
+        "title,url,body,state,createdAt,closedAt,number,repository".to_string(),
+        "--limit".to_string(),
+        SEARCH_RESULT_CAP.to_string(),
+    ]);
+
+    match involvement {
+        IssueInvolvement::Closed => args.extend(["--".to_string(), "reason:completed".to_string()]),
+        IssueInvolvement::Commented => args.extend(["--".to_string(), "-author:@me".to_string()]),
+        IssueInvolvement::Opened => {}
+    }
+
+    args
+}
+
+fn search<T: DeserializeOwned>(host: &str, args: &[String]) -> Result<Vec<T>, AppError> {
+    let output = cancel::output(gh_command()?.env("GH_HOST", host).args(args)).map_err(|e| {
+        AppError::new(
+            ErrorCode::Io,
+            format!("Failed to execute GitHub CLI: {}", e),
+        )
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::from_gh_stderr(&stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let items: Vec<T> = serde_json::from_str(&stdout).map_err(|e| {
+        AppError::new(
+            ErrorCode::Parse,
+            format!("Failed to parse GitHub response: {}", e),
+        )
+    })?;
+
+    progress::page_fetched(items.len());
+    Ok(items)
+}
+
+/// Runs a GraphQL query through `gh api graphql` and returns its `data` object.
+fn graphql(host: &str, query: &str) -> Result<Map<String, Value>, AppError> {
+    let output = cancel::output(
+        gh_command()?
+            .env("GH_HOST", host)
+            .args(["api", "graphql", "-f"])
+            .arg(format!("query={}", query)),
+    )
+    .map_err(|e| {
+        AppError::new(
+            ErrorCode::Io,
+            format!("Failed to execute GitHub CLI: {}", e),
+        )
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::from_gh_stderr(&stderr));
+    }
+
+    let mut response: Map<String, Value> = serde_json::from_slice(&output.stdout).map_err(|e| {
+        AppError::new(
+            ErrorCode::Parse,
+            format!("Failed to parse GitHub response: {}", e),
+        )
+    })?;
+
+    match response.remove("data") {
+        Some(Value::Object(data)) => Ok(data),
+        _ => Err(AppError::new(ErrorCode::Api, "GitHub API returned no data")),
+    }
+}
+
+/// Fetches activity by shelling out to `gh search prs` against a single host.
+pub struct GhCliSource {
+    host: String,
+}
+
+impl GhCliSource {
+    pub fn new(host: impl Into<String>) -> Self {
+        Self { host: host.into() }
+    }
+}
+
+impl ActivitySource for GhCliSource {
+    fn authored_prs(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        let mut args = search_prs_args(
+            "author",
+            date_range,
+            "title,url,body,closedAt,createdAt,number,repository",
+        );
+
+        if let Some(org) = owner {
+            args.insert(4, "--owner".to_string());
+            args.insert(5, org.to_string());
+        }
+
+        search(&self.host, &args)
+    }
+
+    fn search_authored(
+        &self,
+        start: &str,
+        end: &str,
+        owner: Option<&str>,
+    ) -> Result<SearchOutcome<PullRequest>, AppError> {
+        let mut outcome = search_in_windows(start, end, |date_range| {
+            self.authored_prs(date_range, owner)
+        })?;
+        outcome.complete &=
+            pr_details::enrich(&mut outcome.items, |query| graphql(&self.host, query))?;
+        Ok(outcome)
+    }
+
+    fn reviewed_prs(&self, date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+        search(
+            &self.host,
+            &search_prs_args(
+                "reviewed-by",
+                date_range,
+                "title,url,closedAt,createdAt,author,repository",
+            ),
+        )
+    }
+
+    fn search_reviewed(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> Result<SearchOutcome<ReviewedPullRequest>, AppError> {
+        let mut outcome =
+            search_in_windows(start, end, |date_range| self.reviewed_prs(date_range))?;
+        outcome.complete &=
+            review_depth::enrich(&mut outcome.items, |query| graphql(&self.host, query))?;
+        Ok(outcome)
+    }
+
+    fn issues(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+        involvement: IssueInvolvement,
+    ) -> Result<Vec<Issue>, AppError> {
+        search(
+            &self.host,
+            &search_issues_args(date_range, owner, involvement),
+        )
+    }
+
+    fn login(&self) -> Result<Option<String>, AppError> {
+        pr_lookup::viewer(|query| graphql(&self.host, query))
+    }
+
+    fn authored_repositories(&self, date_range: &str) -> Result<Vec<String>, AppError> {
+        search::<SimplePr>(
+            &self.host,
+            &search_prs_args("author", date_range, "repository"),
+        )
+        .map(|prs| {
+            prs.into_iter()
+                .map(|pr| pr.repository.name_with_owner)
+                .collect()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_prs_args() {
+        let args = search_prs_args("reviewed-by", "2024-01-01..2024-12-31", "title,url");
+        assert_eq!(
+            args,
+            vec![
+                "search",
+                "prs",
+                "--reviewed-by",
+                "@me",
+                "--merged-at",
+                "2024-01-01..2024-12-31",
+                "--json",
+                "title,url",
+                "--limit",
+                "1000"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_search_issues_args_commented() {
+        let args = search_issues_args(
+            "2024-01-01..2024-12-31",
+            Some("acme"),
+            IssueInvolvement::Commented,
+        );
+        assert_eq!(
+            args,
+            vec![
+                "search",
+                "issues",
+                "--commenter",
+                "@me",
+                "--updated",
+                "2024-01-01..2024-12-31",
+                "--owner",
+                "acme",
+                "--json",
+                "title,url,body,state,createdAt,closedAt,number,repository",
+                "--limit",
+                "1000",
+                "--",
+                "-author:@me"
+            ]
+        );
+    }
+}
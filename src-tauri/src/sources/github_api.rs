@@ -0,0 +1,374 @@
+use super::{ActivitySource, pr_details, pr_lookup, review_depth};
+use crate::cancel;
+use crate::error::{AppError, ErrorCode};
+use crate::progress;
+use crate::search::{SEARCH_RESULT_CAP, SearchOutcome, search_in_windows};
+use crate::{Issue, IssueInvolvement, PullRequest, ReviewedPullRequest, gh_command};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value, json};
+
+const GITHUB_COM_HOST: &str = "github.com";
+const PAGE_SIZE: usize = 100;
+
+const AUTHORED_PR_FIELDS: &str =
+    "title url body closedAt createdAt number repository { name nameWithOwner }";
+const REVIEWED_PR_FIELDS: &str =
+    "title url closedAt createdAt author { login } repository { name nameWithOwner }";
+const ISSUE_FIELDS: &str =
+    "title url body state createdAt closedAt number repository { name nameWithOwner }";
+
+#[derive(Debug, Deserialize)]
+struct GraphQlResponse<T> {
+    data: Option<T>,
+    errors: Option<Vec<GraphQlError>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GraphQlError {
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchData<T> {
+    search: SearchConnection<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchConnection<T> {
+    #[serde(rename = "pageInfo")]
+    page_info: PageInfo,
+    nodes: Vec<T>,
+}
+
+#[derive(Debug, Deserialize)]
+struct PageInfo {
+    #[serde(rename = "hasNextPage")]
+    has_next_page: bool,
+    #[serde(rename = "endCursor")]
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryNode {
+    repository: RepositoryName,
+}
+
+#[derive(Debug, Deserialize)]
+struct RepositoryName {
+    #[serde(rename = "nameWithOwner")]
+    name_with_owner: String,
+}
+
+/// Resolves the token used for the GitHub API: the one saved in settings,
+/// or the token `gh` is already logged in with for `host`.
+pub fn resolve_token(settings_token: Option<&str>, host: &str) -> Result<String, AppError> {
+    if let Some(token) = settings_token.filter(|t| !t.trim().is_empty()) {
+        return Ok(token.trim().to_string());
+    }
+
+    let output = gh_command()
+        .and_then(|mut cmd| {
+            cancel::output(cmd.args(["auth", "token", "--hostname", host])).map_err(|e| {
+                AppError::new(
+                    ErrorCode::Io,
+                    format!("Failed to execute GitHub CLI: {}", e),
+                )
+            })
+        })
+        .map_err(|e| AppError {
+            message: format!(
+                "No GitHub token configured and `gh auth token` failed: {}",
+                e.message
+            ),
+            ..e
+        })?;
+
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if !output.status.success() || token.is_empty() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::new(
+            ErrorCode::NotAuthenticated,
+            format!(
+                "No GitHub token configured and `gh auth token` failed: {}",
+                stderr.trim()
+            ),
+        )
+        .with_host(host));
+    }
+
+    Ok(token)
+}
+
+/// GraphQL endpoint for a host: github.com uses `api.github.com`, while
+/// GitHub Enterprise Server serves the API under `/api/graphql`.
+fn graphql_endpoint(host: &str) -> String {
+    if host.eq_ignore_ascii_case(GITHUB_COM_HOST) {
+        "https://api.github.com/graphql".to_string()
+    } else {
+        format!("https://{}/api/graphql", host.trim_end_matches('/'))
+    }
+}
+
+fn search_query(node_type: &str, fields: &str) -> String {
+    format!(
+        "query($q: String!, $first: Int!, $after: String) {{ \
+           search(query: $q, type: ISSUE, first: $first, after: $after) {{ \
+             pageInfo {{ hasNextPage endCursor }} \
+             nodes {{ ... on {} {{ {} }} }} \
+           }} \
+         }}",
+        node_type, fields
+    )
+}
+
+pub struct GitHubApiClient {
+    client: reqwest::blocking::Client,
+    endpoint: String,
+    token: String,
+}
+
+impl GitHubApiClient {
+    pub fn new(token: String, host: &str) -> Self {
+        Self {
+            client: reqwest::blocking::Client::new(),
+            endpoint: graphql_endpoint(host),
+            token,
+        }
+    }
+
+    fn graphql<T: DeserializeOwned>(
+        &self,
+        query: &str,
+        variables: serde_json::Value,
+    ) -> Result<T, AppError> {
+        let response = self
+            .client
+            .post(&self.endpoint)
+            .header("Authorization", format!("Bearer {}", self.token))
+            .header("User-Agent", "MyImpact")
+            .json(&json!({ "query": query, "variables": variables }))
+            .send()
+            .map_err(|e| AppError::from_request("GitHub API", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let error_text = response.text().unwrap_or_default();
+            return Err(AppError::from_response_parts(
+                "GitHub API",
+                status,
+                &headers,
+                &error_text,
+            ));
+        }
+
+        let body: GraphQlResponse<T> = response.json().map_err(|e| {
+            AppError::new(
+                ErrorCode::Parse,
+                format!("Failed to parse GitHub response: {}", e),
+            )
+        })?;
+
+        if let Some(errors) = body.errors.filter(|errors| !errors.is_empty()) {
+            let messages: Vec<String> = errors.into_iter().map(|e| e.message).collect();
+            let message = format!("GitHub API error: {}", messages.join("; "));
+            let code = if message.to_lowercase().contains("rate limit") {
+                ErrorCode::RateLimited
+            } else {
+                ErrorCode::Api
+            };
+            return Err(AppError::new(code, message));
+        }
+
+        body.data
+            .ok_or_else(|| AppError::new(ErrorCode::Api, "GitHub API returned no data"))
+    }
+
+    /// Runs a search query, following cursors until GitHub runs out of pages
+    /// or the search result cap is reached.
+    fn search<T: DeserializeOwned>(
+        &self,
+        search: &str,
+        node_type: &str,
+        fields: &str,
+    ) -> Result<Vec<T>, AppError> {
+        let query = search_query(node_type, fields);
+        let mut items = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let data: SearchData<T> = self.graphql(
+                &query,
+                json!({ "q": search, "first": PAGE_SIZE, "after": after }),
+            )?;
+
+            progress::page_fetched(data.search.nodes.len());
+            items.extend(data.search.nodes);
+
+            if !data.search.page_info.has_next_page || items.len() >= SEARCH_RESULT_CAP {
+                break;
+            }
+            after = data.search.page_info.end_cursor;
+        }
+
+        Ok(items)
+    }
+}
+
+impl ActivitySource for GitHubApiClient {
+    fn authored_prs(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        let mut search = format!("is:pr is:merged author:@me merged:{}", date_range);
+        if let Some(owner) = owner {
+            search.push_str(&format!(" user:{}", owner));
+        }
+
+        self.search(&search, "PullRequest", AUTHORED_PR_FIELDS)
+    }
+
+    fn search_authored(
+        &self,
+        start: &str,
+        end: &str,
+        owner: Option<&str>,
+    ) -> Result<SearchOutcome<PullRequest>, AppError> {
+        let mut outcome = search_in_windows(start, end, |date_range| {
+            self.authored_prs(date_range, owner)
+        })?;
+        outcome.complete &= pr_details::enrich(&mut outcome.items, |query| {
+            self.graphql::<Map<String, Value>>(query, json!({}))
+        })?;
+        Ok(outcome)
+    }
+
+    fn reviewed_prs(&self, date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+        let search = format!("is:pr is:merged reviewed-by:@me merged:{}", date_range);
+        self.search(&search, "PullRequest", REVIEWED_PR_FIELDS)
+    }
+
+    fn search_reviewed(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> Result<SearchOutcome<ReviewedPullRequest>, AppError> {
+        let mut outcome =
+            search_in_windows(start, end, |date_range| self.reviewed_prs(date_range))?;
+        outcome.complete &= review_depth::enrich(&mut outcome.items, |query| {
+            self.graphql::<Map<String, Value>>(query, json!({}))
+        })?;
+        Ok(outcome)
+    }
+
+    fn issues(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+        involvement: IssueInvolvement,
+    ) -> Result<Vec<Issue>, AppError> {
+        let mut search = match involvement {
+            IssueInvolvement::Opened => format!("is:issue author:@me created:{}", date_range),
+            IssueInvolvement::Closed => format!(
+                "is:issue is:closed reason:completed assignee:@me closed:{}",
+                date_range
+            ),
+            IssueInvolvement::Commented => {
+                format!("is:issue commenter:@me -author:@me updated:{}", date_range)
+            }
+        };
+        if let Some(owner) = owner {
+            search.push_str(&format!(" user:{}", owner));
+        }
+
+        self.search(&search, "Issue", ISSUE_FIELDS)
+    }
+
+    fn login(&self) -> Result<Option<String>, AppError> {
+        pr_lookup::viewer(|query| self.graphql::<Map<String, Value>>(query, json!({})))
+    }
+
+    fn authored_repositories(&self, date_range: &str) -> Result<Vec<String>, AppError> {
+        let search = format!("is:pr is:merged author:@me merged:{}", date_range);
+        let repositories: Vec<RepositoryNode> =
+            self.search(&search, "PullRequest", "repository { nameWithOwner }")?;
+
+        Ok(repositories
+            .into_iter()
+            .map(|node| node.repository.name_with_owner)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_search_query_includes_fields() {
+        let query = search_query("PullRequest", AUTHORED_PR_FIELDS);
+        assert!(query.contains("search(query: $q, type: ISSUE"));
+        assert!(query.contains("... on PullRequest { title url body"));
+    }
+
+    #[test]
+    fn test_search_response_deserialization() {
+        let json = r#"{
+            "data": {
+                "search": {
+                    "pageInfo": { "hasNextPage": true, "endCursor": "Y3Vyc29yOjEwMA==" },
+                    "nodes": [{
+                        "title": "Add API client",
+                        "url": "https://github.com/org/repo/pull/7",
+                        "body": null,
+                        "closedAt": "2024-11-15T10:00:00Z",
+                        "createdAt": "2024-11-14T08:00:00Z",
+                        "number": 7,
+                        "repository": { "name": "repo", "nameWithOwner": "org/repo" }
+                    }]
+                }
+            }
+        }"#;
+
+        let response: GraphQlResponse<SearchData<PullRequest>> =
+            serde_json::from_str(json).unwrap();
+        let search = response.data.unwrap().search;
+
+        assert!(search.page_info.has_next_page);
+        assert_eq!(
+            search.page_info.end_cursor.as_deref(),
+            Some("Y3Vyc29yOjEwMA==")
+        );
+        assert_eq!(search.nodes[0].repository.name_with_owner, "org/repo");
+    }
+
+    #[test]
+    fn test_error_response_deserialization() {
+        let json = r#"{"data": null, "errors": [{"message": "Bad credentials"}]}"#;
+        let response: GraphQlResponse<SearchData<PullRequest>> =
+            serde_json::from_str(json).unwrap();
+
+        assert!(response.data.is_none());
+        assert_eq!(response.errors.unwrap()[0].message, "Bad credentials");
+    }
+
+    #[test]
+    fn test_resolve_token_prefers_settings() {
+        let token = resolve_token(Some("  ghp_test  "), "github.com").unwrap();
+        assert_eq!(token, "ghp_test");
+    }
+
+    #[test]
+    fn test_graphql_endpoint() {
+        assert_eq!(
+            graphql_endpoint("github.com"),
+            "https://api.github.com/graphql"
+        );
+        assert_eq!(
+            graphql_endpoint("github.acme.com"),
+            "https://github.acme.com/api/graphql"
+        );
+    }
+}
@@ -0,0 +1,343 @@
+use super::{ActivitySource, owned_by};
+use crate::error::{AppError, ErrorCode};
+use crate::progress;
+use crate::{Author, PullRequest, Repository, ReviewedPullRequest};
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::sync::OnceLock;
+
+const PAGE_SIZE: usize = 100;
+
+/// Query for one page of merged merge requests matching `filter` that may have
+/// been merged on or after `start`.
+///
+/// A merge request is updated when it is merged, so one merged since `start` was
+/// last updated since then too. There is no upper bound: a merge request merged
+/// in the range can be touched again at any later time (a comment, a label, a
+/// deleted branch), so the end of the range is checked on `merged_at` instead.
+fn merged_requests_query<'a>(
+    start: &str,
+    page: String,
+    filter: &(&'a str, String),
+) -> Vec<(&'a str, String)> {
+    vec![
+        ("scope", "all".to_string()),
+        ("state", "merged".to_string()),
+        ("updated_after", format!("{}T00:00:00Z", start)),
+        ("per_page", PAGE_SIZE.to_string()),
+        ("page", page),
+        (filter.0, filter.1.clone()),
+    ]
+}
+
+#[derive(Debug, Deserialize)]
+struct GitLabUser {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MergeRequest {
+    iid: i32,
+    title: String,
+    description: Option<String>,
+    web_url: String,
+    created_at: String,
+    merged_at: Option<String>,
+    author: GitLabUser,
+    references: References,
+    #[serde(default)]
+    labels: Vec<String>,
+    milestone: Option<Milestone>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Milestone {
+    title: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct References {
+    /// `group/subgroup/project!123`
+    full: String,
+}
+
+impl MergeRequest {
+    fn repository(&self) -> Repository {
+        let path = self
+            .references
+            .full
+            .rsplit_once('!')
+            .map(|(path, _)| path)
+            .unwrap_or(&self.references.full);
+        let name = path.rsplit('/').next().unwrap_or(path);
+
+        Repository {
+            name: name.to_string(),
+            name_with_owner: path.to_string(),
+            host: None,
+        }
+    }
+
+    fn merged_in(&self, start: &str, end: &str) -> bool {
+        self.merged_at
+            .as_deref()
+            .and_then(|merged_at| merged_at.get(..10))
+            .is_some_and(|day| day >= start && day <= end)
+    }
+
+    fn into_pull_request(self) -> PullRequest {
+        let repository = self.repository();
+        PullRequest {
+            title: self.title,
+            url: self.web_url,
+            body: self.description.filter(|d| !d.is_empty()),
+            closed_at: self.merged_at.unwrap_or_default(),
+            created_at: Some(self.created_at),
+            number: Some(self.iid),
+            repository,
+            size: None,
+            labels: self.labels,
+            milestone: self.milestone.map(|m| m.title),
+            closing_issues: Vec::new(),
+        }
+    }
+
+    fn into_reviewed_pull_request(self) -> ReviewedPullRequest {
+        let repository = self.repository();
+        ReviewedPullRequest {
+            title: self.title,
+            url: self.web_url,
+            closed_at: self.merged_at,
+            created_at: self.created_at,
+            author: Author {
+                login: self.author.username,
+            },
+            repository,
+            review: None,
+        }
+    }
+}
+
+/// Fetches merged merge requests from a GitLab instance through its REST API,
+/// mapped onto the same types as GitHub pull requests.
+pub struct GitLabSource {
+    client: reqwest::blocking::Client,
+    api_url: String,
+    token: String,
+    username: OnceLock<String>,
+}
+
+impl GitLabSource {
+    pub fn new(base_url: &str, token: String) -> Self {
+        Self {
+            client: reqwest::blocking::Client::new(),
+            api_url: format!("{}/api/v4", base_url.trim_end_matches('/')),
+            token,
+            username: OnceLock::new(),
+        }
+    }
+
+    fn get<T: DeserializeOwned>(
+        &self,
+        path: &str,
+        query: &[(&str, String)],
+    ) -> Result<(T, Option<String>), AppError> {
+        let response = self
+            .client
+            .get(format!("{}{}", self.api_url, path))
+            .header("PRIVATE-TOKEN", &self.token)
+            .query(query)
+            .send()
+            .map_err(|e| AppError::from_request("GitLab API", e))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let error_text = response.text().unwrap_or_default();
+            return Err(AppError::from_response_parts(
+                "GitLab API",
+                status,
+                &headers,
+                &error_text,
+            ));
+        }
+
+        let next_page = response
+            .headers()
+            .get("x-next-page")
+            .and_then(|v| v.to_str().ok())
+            .filter(|v| !v.is_empty())
+            .map(|v| v.to_string());
+
+        let body = response.json().map_err(|e| {
+            AppError::new(
+                ErrorCode::Parse,
+                format!("Failed to parse GitLab response: {}", e),
+            )
+        })?;
+
+        Ok((body, next_page))
+    }
+
+    fn username(&self) -> Result<&str, AppError> {
+        if let Some(username) = self.username.get() {
+            return Ok(username);
+        }
+
+        let (user, _) = self.get::<GitLabUser>("/user", &[])?;
+        Ok(self.username.get_or_init(|| user.username))
+    }
+
+    /// Lists merged merge requests matching `filter` that were merged inside `date_range`.
+    ///
+    /// GitLab can't filter on merge date, so this asks for everything updated
+    /// since the range began (see [`merged_requests_query`]) and checks `merged_at` locally.
+    fn merged_requests(
+        &self,
+        date_range: &str,
+        filter: (&str, String),
+    ) -> Result<Vec<MergeRequest>, AppError> {
+        let (start, end) = date_range
+            .split_once("..")
+            .unwrap_or((date_range, date_range));
+        let mut merged = Vec::new();
+        let mut page = "1".to_string();
+
+        loop {
+            let query = merged_requests_query(start, page, &filter);
+            let (batch, next_page) = self.get::<Vec<MergeRequest>>("/merge_requests", &query)?;
+
+            progress::page_fetched(batch.len());
+            merged.extend(batch.into_iter().filter(|mr| mr.merged_in(start, end)));
+
+            match next_page {
+                Some(next) => page = next,
+                None => break,
+            }
+        }
+
+        Ok(merged)
+    }
+}
+
+impl ActivitySource for GitLabSource {
+    fn authored_prs(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        let username = self.username()?.to_string();
+        let requests = self.merged_requests(date_range, ("author_username", username))?;
+
+        Ok(requests
+            .into_iter()
+            .map(MergeRequest::into_pull_request)
+            .filter(|pr| owned_by(&pr.repository, owner))
+            .collect())
+    }
+
+    fn reviewed_prs(&self, date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+        let username = self.username()?.to_string();
+        let requests = self.merged_requests(date_range, ("reviewer_username", username))?;
+
+        Ok(requests
+            .into_iter()
+            .map(MergeRequest::into_reviewed_pull_request)
+            .collect())
+    }
+
+    fn login(&self) -> Result<Option<String>, AppError> {
+        self.username().map(|username| Some(username.to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample_merge_request() -> MergeRequest {
+        serde_json::from_str(
+            r#"{
+                "iid": 42,
+                "title": "Add caching layer",
+                "description": "",
+                "web_url": "https://gitlab.acme.com/platform/infra/cache/-/merge_requests/42",
+                "created_at": "2024-03-01T09:00:00.000Z",
+                "merged_at": "2024-03-04T15:30:00.000Z",
+                "author": {"username": "teammate"},
+                "references": {"full": "platform/infra/cache!42"},
+                "labels": ["perf"],
+                "milestone": {"title": "16.4"}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_request_repository_uses_project_path() {
+        let repository = sample_merge_request().repository();
+
+        assert_eq!(repository.name, "cache");
+        assert_eq!(repository.name_with_owner, "platform/infra/cache");
+    }
+
+    #[test]
+    fn test_merge_request_into_pull_request() {
+        let pr = sample_merge_request().into_pull_request();
+
+        assert_eq!(pr.number, Some(42));
+        assert_eq!(pr.closed_at, "2024-03-04T15:30:00.000Z");
+        assert!(pr.body.is_none());
+        assert_eq!(pr.labels, vec!["perf"]);
+        assert_eq!(pr.milestone.as_deref(), Some("16.4"));
+    }
+
+    #[test]
+    fn test_merge_request_into_reviewed_pull_request() {
+        let pr = sample_merge_request().into_reviewed_pull_request();
+
+        assert_eq!(pr.author.login, "teammate");
+        assert_eq!(pr.repository.name_with_owner, "platform/infra/cache");
+    }
+
+    #[test]
+    fn test_merged_in_range() {
+        let mr = sample_merge_request();
+
+        assert!(mr.merged_in("2024-03-01", "2024-03-31"));
+        assert!(!mr.merged_in("2024-04-01", "2024-04-30"));
+    }
+
+    #[test]
+    fn test_keeps_merge_requests_updated_after_the_range() {
+        let query = merged_requests_query(
+            "2024-03-01",
+            "1".to_string(),
+            &("author_username", "me".to_string()),
+        );
+        let touched_later: MergeRequest = serde_json::from_str(
+            r#"{
+                "iid": 7,
+                "title": "Fix login",
+                "description": null,
+                "web_url": "https://gitlab.acme.com/platform/web/-/merge_requests/7",
+                "created_at": "2024-03-01T09:00:00.000Z",
+                "merged_at": "2024-03-20T15:30:00.000Z",
+                "updated_at": "2024-09-02T08:00:00.000Z",
+                "author": {"username": "me"},
+                "references": {"full": "platform/web!7"}
+            }"#,
+        )
+        .unwrap();
+
+        assert!(query.contains(&("updated_after", "2024-03-01T00:00:00Z".to_string())));
+        assert!(query.iter().all(|(key, _)| *key != "updated_before"));
+        assert!(touched_later.merged_in("2024-03-01", "2024-03-31"));
+    }
+
+    #[test]
+    fn test_api_url_trims_trailing_slash() {
+        let source = GitLabSource::new("https://gitlab.acme.com/", "token".to_string());
+        assert_eq!(source.api_url, "https://gitlab.acme.com/api/v4");
+    }
+}
@@ -0,0 +1,418 @@
+use super::{ActivitySource, owned_by};
+use crate::cancel;
+use crate::error::{AppError, ErrorCode};
+use crate::{PullRequest, PullRequestSize, Repository, ReviewedPullRequest};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
+use std::path::{Path, PathBuf};
+use tokio::process::Command;
+
+const FIELD_SEPARATOR: char = '\x1f';
+const RECORD_SEPARATOR: char = '\x1e';
+const LOG_FORMAT: &str = "--format=%x1e%H%x1f%P%x1f%ae%x1f%aI%x1f%s";
+
+/// How commits from local repositories are grouped into pull-request-shaped units.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum LocalGrouping {
+    /// One unit per merge commit that brought the user's commits in,
+    /// with unmerged or directly committed work grouped by day.
+    #[default]
+    Merge,
+    /// One unit per repository and day.
+    Day,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Commit {
+    sha: String,
+    parents: Vec<String>,
+    email: String,
+    date: String,
+    subject: String,
+    additions: u32,
+    deletions: u32,
+    files: Vec<String>,
+}
+
+impl Commit {
+    fn day(&self) -> &str {
+        self.date.get(..10).unwrap_or(&self.date)
+    }
+}
+
+/// Parses `git log --numstat` output where each record is a header line in
+/// [`LOG_FORMAT`] followed by one `added<TAB>deleted<TAB>path` line per file.
+fn parse_log(output: &str) -> Vec<Commit> {
+    output
+        .split(RECORD_SEPARATOR)
+        .filter_map(|record| {
+            let mut lines = record.trim().lines();
+            let mut fields = lines.next()?.split(FIELD_SEPARATOR);
+            let sha = fields.next().filter(|s| !s.is_empty())?;
+            let parents = fields.next()?;
+            let email = fields.next()?;
+            let date = fields.next()?;
+            let subject = fields.next().unwrap_or_default();
+
+            let mut commit = Commit {
+                sha: sha.to_string(),
+                parents: parents.split_whitespace().map(|p| p.to_string()).collect(),
+                email: email.to_string(),
+                date: date.to_string(),
+                subject: subject.to_string(),
+                additions: 0,
+                deletions: 0,
+                files: Vec::new(),
+            };
+
+            for line in lines {
+                let mut stat = line.splitn(3, '\t');
+                let (Some(added), Some(deleted), Some(path)) =
+                    (stat.next(), stat.next(), stat.next())
+                else {
+                    continue;
+                };
+                // Binary files show `-` instead of line counts.
+                commit.additions += added.parse::<u32>().unwrap_or(0);
+                commit.deletions += deleted.parse::<u32>().unwrap_or(0);
+                commit.files.push(path.to_string());
+            }
+
+            Some(commit)
+        })
+        .collect()
+}
+
+fn git(repo: &Path, args: &[&str]) -> Result<String, AppError> {
+    let output = cancel::output(Command::new("git").arg("-C").arg(repo).args(args))
+        .map_err(|e| AppError::new(ErrorCode::Io, format!("Failed to execute git: {}", e)))?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(AppError::new(
+            ErrorCode::Io,
+            format!("git error in {}: {}", repo.display(), stderr.trim()),
+        ));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn repository_for(path: &Path) -> Repository {
+    let name = path
+        .file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+
+    Repository {
+        name_with_owner: format!("local/{}", name),
+        name,
+        host: None,
+    }
+}
+
+/// Builds one pull-request-shaped entry out of a group of commits (oldest first).
+fn work_unit(
+    path: &Path,
+    title: String,
+    commits: &[Commit],
+    anchor: &Commit,
+    closed_at: &str,
+) -> PullRequest {
+    let body = commits
+        .iter()
+        .map(|c| format!("- {} ({})", c.subject, &c.sha[..c.sha.len().min(7)]))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let files: HashSet<&str> = commits
+        .iter()
+        .flat_map(|c| c.files.iter().map(|f| f.as_str()))
+        .collect();
+
+    PullRequest {
+        title,
+        url: format!("file://{}#{}", path.display(), anchor.sha),
+        body: Some(body),
+        closed_at: closed_at.to_string(),
+        created_at: commits.first().map(|c| c.date.clone()),
+        number: None,
+        repository: repository_for(path),
+        size: Some(PullRequestSize {
+            additions: commits.iter().map(|c| c.additions).sum(),
+            deletions: commits.iter().map(|c| c.deletions).sum(),
+            changed_files: files.len() as u32,
+            commits: commits.len() as u32,
+        }),
+        labels: Vec::new(),
+        milestone: None,
+        closing_issues: Vec::new(),
+    }
+}
+
+fn group_by_day(path: &Path, commits: Vec<Commit>) -> Vec<PullRequest> {
+    let mut days: BTreeMap<String, Vec<Commit>> = BTreeMap::new();
+    for commit in commits {
+        days.entry(commit.day().to_string())
+            .or_default()
+            .push(commit);
+    }
+
+    days.into_values()
+        .map(|mut commits| {
+            commits.sort_by(|a, b| a.date.cmp(&b.date));
+            let last = commits.last().unwrap().clone();
+            let title = match commits.len() {
+                1 => last.subject.clone(),
+                n => format!("{} (+{} more commits)", commits[0].subject, n - 1),
+            };
+            work_unit(path, title, &commits, &last, &last.date)
+        })
+        .collect()
+}
+
+/// Scans local git repositories for commits authored by any of the user's emails.
+pub struct LocalGitSource {
+    repositories: Vec<PathBuf>,
+    emails: Vec<String>,
+    grouping: LocalGrouping,
+}
+
+impl LocalGitSource {
+    pub fn new(repositories: &[String], emails: &[String], grouping: LocalGrouping) -> Self {
+        Self {
+            repositories: repositories.iter().map(PathBuf::from).collect(),
+            emails: emails.iter().map(|e| e.trim().to_lowercase()).collect(),
+            grouping,
+        }
+    }
+
+    fn is_own(&self, commit: &Commit) -> bool {
+        self.emails.contains(&commit.email.to_lowercase())
+    }
+
+    fn scan_repository(
+        &self,
+        path: &Path,
+        start: &str,
+        end: &str,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        let since = format!("--since={} 00:00:00", start);
+        let until = format!("--until={} 23:59:59", end);
+        let log = git(
+            path,
+            &["log", "--all", "--numstat", &since, &until, LOG_FORMAT],
+        )?;
+
+        let (mut merges, own): (Vec<Commit>, Vec<Commit>) = parse_log(&log)
+            .into_iter()
+            .filter(|c| c.day() >= start && c.day() <= end)
+            .partition(|c| c.parents.len() > 1);
+        let mut own: Vec<Commit> = own.into_iter().filter(|c| self.is_own(c)).collect();
+
+        if own.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut units = Vec::new();
+
+        if self.grouping == LocalGrouping::Merge {
+            merges.sort_by(|a, b| a.date.cmp(&b.date));
+
+            for merge in &merges {
+                let branch_range = format!("{}..{}", merge.parents[0], merge.parents[1]);
+                let branch: HashSet<String> = git(path, &["rev-list", &branch_range])?
+                    .lines()
+                    .map(|sha| sha.to_string())
+                    .collect();
+
+                let (mut merged, rest): (Vec<Commit>, Vec<Commit>) =
+                    own.into_iter().partition(|c| branch.contains(&c.sha));
+                own = rest;
+
+                if merged.is_empty() {
+                    continue;
+                }
+
+                merged.sort_by(|a, b| a.date.cmp(&b.date));
+                units.push(work_unit(
+                    path,
+                    merge.subject.clone(),
+                    &merged,
+                    merge,
+                    &merge.date,
+                ));
+            }
+        }
+
+        units.extend(group_by_day(path, own));
+        Ok(units)
+    }
+}
+
+impl ActivitySource for LocalGitSource {
+    fn authored_prs(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        let (start, end) = date_range
+            .split_once("..")
+            .unwrap_or((date_range, date_range));
+        let mut units = Vec::new();
+
+        for path in &self.repositories {
+            units.extend(self.scan_repository(path, start, end)?);
+        }
+
+        Ok(units
+            .into_iter()
+            .filter(|pr| owned_by(&pr.repository, owner))
+            .collect())
+    }
+
+    fn reviewed_prs(&self, _date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn commit(sha: &str, date: &str, subject: &str) -> Commit {
+        Commit {
+            sha: sha.to_string(),
+            parents: vec![],
+            email: "me@example.com".to_string(),
+            date: date.to_string(),
+            subject: subject.to_string(),
+            additions: 0,
+            deletions: 0,
+            files: vec![],
+        }
+    }
+
+    #[test]
+    fn test_parse_log() {
+        let output = "\x1eabc123\x1fdef456 789abc\x1fMe@Example.com\x1f2024-03-04T15:30:00+02:00\x1fMerge branch 'feature'\n\
+                      \x1edef456\x1f\x1fme@example.com\x1f2024-03-04T10:00:00+02:00\x1fAdd feature\n\n\
+                      12\t3\tsrc/feature.rs\n\
+                      -\t-\tassets/logo.png\n";
+
+        let commits = parse_log(output);
+
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].parents, vec!["def456", "789abc"]);
+        assert_eq!(commits[0].subject, "Merge branch 'feature'");
+        assert!(commits[0].files.is_empty());
+        assert!(commits[1].parents.is_empty());
+        assert_eq!(commits[1].day(), "2024-03-04");
+        assert_eq!((commits[1].additions, commits[1].deletions), (12, 3));
+        assert_eq!(commits[1].files, vec!["src/feature.rs", "assets/logo.png"]);
+    }
+
+    #[test]
+    fn test_group_by_day() {
+        let path = Path::new("/work/billing");
+        let units = group_by_day(
+            path,
+            vec![
+                commit("c3", "2024-03-05T09:00:00Z", "Fix typo"),
+                commit("c2", "2024-03-04T16:00:00Z", "Add tests"),
+                commit("c1", "2024-03-04T10:00:00Z", "Add invoice export"),
+            ],
+        );
+
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0].title, "Add invoice export (+1 more commits)");
+        assert_eq!(units[0].closed_at, "2024-03-04T16:00:00Z");
+        assert_eq!(units[0].created_at.as_deref(), Some("2024-03-04T10:00:00Z"));
+        assert_eq!(units[0].url, "file:///work/billing#c2");
+        assert_eq!(units[0].size.unwrap().commits, 2);
+        assert_eq!(units[1].title, "Fix typo");
+        assert_eq!(units[1].repository.name_with_owner, "local/billing");
+    }
+
+    #[test]
+    fn test_is_own_ignores_case() {
+        let source =
+            LocalGitSource::new(&[], &[" Me@Example.com ".to_string()], LocalGrouping::Day);
+
+        assert!(source.is_own(&commit("c1", "2024-03-04T10:00:00Z", "Change")));
+    }
+
+    #[test]
+    fn test_local_grouping_serialization() {
+        assert_eq!(
+            serde_json::to_string(&LocalGrouping::Day).unwrap(),
+            r#""day""#
+        );
+    }
+
+    #[test]
+    fn test_merge_grouping_in_repository() {
+        let dir = std::env::temp_dir().join(format!("myimpact-local-git-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let run = |args: &[&str], date: &str| {
+            let status = std::process::Command::new("git")
+                .arg("-C")
+                .arg(&dir)
+                .args(["-c", "user.name=Me", "-c", "user.email=me@example.com"])
+                .args(args)
+                .env("GIT_AUTHOR_DATE", date)
+                .env("GIT_COMMITTER_DATE", date)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+
+        run(&["init", "-q", "-b", "main"], "2024-03-01T09:00:00Z");
+        run(
+            &["commit", "-q", "--allow-empty", "-m", "Initial commit"],
+            "2024-03-01T09:00:00Z",
+        );
+        run(&["checkout", "-q", "-b", "feature"], "2024-03-02T09:00:00Z");
+        run(
+            &["commit", "-q", "--allow-empty", "-m", "Add exporter"],
+            "2024-03-02T09:00:00Z",
+        );
+        run(
+            &["commit", "-q", "--allow-empty", "-m", "Test exporter"],
+            "2024-03-03T09:00:00Z",
+        );
+        run(&["checkout", "-q", "main"], "2024-03-04T09:00:00Z");
+        run(
+            &["commit", "-q", "--allow-empty", "-m", "Hotfix"],
+            "2024-03-04T09:00:00Z",
+        );
+        run(
+            &[
+                "merge",
+                "-q",
+                "--no-ff",
+                "-m",
+                "Merge branch 'feature'",
+                "feature",
+            ],
+            "2024-03-05T09:00:00Z",
+        );
+
+        let source = LocalGitSource::new(
+            &[dir.display().to_string()],
+            &["me@example.com".to_string()],
+            LocalGrouping::Merge,
+        );
+        let units = source.authored_prs("2024-03-02..2024-03-31", None).unwrap();
+
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(units.len(), 2);
+        assert_eq!(units[0].title, "Merge branch 'feature'");
+        assert!(units[0].body.as_ref().unwrap().contains("Add exporter"));
+        assert!(units[0].body.as_ref().unwrap().contains("Test exporter"));
+        assert_eq!(units[1].title, "Hotfix");
+    }
+}
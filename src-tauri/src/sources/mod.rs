@@ -0,0 +1,143 @@
+mod cached;
+mod fixture;
+mod gh_cli;
+mod github_api;
+mod gitlab;
+mod local_git;
+mod multi_host;
+mod pr_details;
+mod pr_lookup;
+mod review_depth;
+mod unavailable;
+
+pub use cached::CachedSource;
+pub use fixture::FixtureSource;
+pub use gh_cli::GhCliSource;
+pub use github_api::{GitHubApiClient, resolve_token};
+pub use gitlab::GitLabSource;
+pub use local_git::{LocalGitSource, LocalGrouping};
+pub use multi_host::MultiHostSource;
+pub use unavailable::UnavailableSource;
+
+use crate::error::AppError;
+use crate::search::{SearchOutcome, search_in_windows};
+use crate::{Issue, IssueInvolvement, PullRequest, Repository, ReviewedPullRequest};
+
+/// The owner of a repository: the first segment of its `nameWithOwner`, which
+/// for nested GitLab groups (`platform/infra/cache`) is the top-level group.
+pub fn owner_of(name_with_owner: &str) -> &str {
+    name_with_owner.split('/').next().unwrap_or_default()
+}
+
+/// Whether a repository belongs to `owner` (see [`owner_of`]).
+/// Always true when no owner is given.
+fn owned_by(repository: &Repository, owner: Option<&str>) -> bool {
+    owner.is_none_or(|owner| owner_of(&repository.name_with_owner).eq_ignore_ascii_case(owner))
+}
+
+/// A provider of pull request activity for the signed-in user.
+///
+/// The per-window methods receive a single `YYYY-MM-DD..YYYY-MM-DD` window.
+/// The `search_*` methods cover a whole `start..end` range, splitting it around
+/// the search result cap; sources that query several hosts or cache results
+/// override them so the cap and completeness are tracked where the search runs.
+pub trait ActivitySource {
+    /// Merged pull requests authored by the user, optionally limited to one owner.
+    fn authored_prs(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+    ) -> Result<Vec<PullRequest>, AppError>;
+
+    /// Merged pull requests the user reviewed.
+    fn reviewed_prs(&self, date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError>;
+
+    /// Issues the user was involved with in the given way.
+    ///
+    /// Sources without an issue tracker return nothing.
+    fn issues(
+        &self,
+        _date_range: &str,
+        _owner: Option<&str>,
+        _involvement: IssueInvolvement,
+    ) -> Result<Vec<Issue>, AppError> {
+        Ok(Vec::new())
+    }
+
+    /// Login of the account the source fetches as, so cached results are kept per account.
+    /// `None` for sources that aren't tied to an account.
+    fn login(&self) -> Result<Option<String>, AppError> {
+        Ok(None)
+    }
+
+    /// When results were served from the offline cache because fetching failed,
+    /// the time (RFC 3339) that cache was last synced, or the earliest of those
+    /// times when several caches were used.
+    fn as_of(&self) -> Option<String> {
+        None
+    }
+
+    /// `owner/name` of the repository behind each authored pull request.
+    ///
+    /// Sources that can fetch this more cheaply than full pull requests should override it.
+    fn authored_repositories(&self, date_range: &str) -> Result<Vec<String>, AppError> {
+        self.authored_prs(date_range, None).map(|prs| {
+            prs.into_iter()
+                .map(|pr| pr.repository.name_with_owner)
+                .collect()
+        })
+    }
+
+    /// [`authored_prs`](Self::authored_prs) over the whole range, with size, labels,
+    /// milestone and closing issues filled in where the source can. Sources look
+    /// these up once on the final results, so a capped window isn't enriched for nothing.
+    fn search_authored(
+        &self,
+        start: &str,
+        end: &str,
+        owner: Option<&str>,
+    ) -> Result<SearchOutcome<PullRequest>, AppError> {
+        search_in_windows(start, end, |date_range| {
+            self.authored_prs(date_range, owner)
+        })
+    }
+
+    /// [`reviewed_prs`](Self::reviewed_prs) over the whole range, with `review`
+    /// filled in where the source can, once on the final results.
+    fn search_reviewed(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> Result<SearchOutcome<ReviewedPullRequest>, AppError> {
+        search_in_windows(start, end, |date_range| self.reviewed_prs(date_range))
+    }
+
+    fn search_issues(
+        &self,
+        start: &str,
+        end: &str,
+        owner: Option<&str>,
+        involvement: IssueInvolvement,
+    ) -> Result<SearchOutcome<Issue>, AppError> {
+        search_in_windows(start, end, |date_range| {
+            self.issues(date_range, owner, involvement)
+        })
+    }
+
+    /// The owner (see [`owner_of`]) of the repository behind each authored pull
+    /// request in `start..end`.
+    fn search_owners(&self, start: &str, end: &str) -> Result<SearchOutcome<String>, AppError> {
+        let outcome = search_in_windows(start, end, |date_range| {
+            self.authored_repositories(date_range)
+        })?;
+
+        Ok(SearchOutcome {
+            items: outcome
+                .items
+                .iter()
+                .map(|name_with_owner| owner_of(name_with_owner).to_string())
+                .collect(),
+            complete: outcome.complete,
+        })
+    }
+}
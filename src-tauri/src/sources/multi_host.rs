@@ -0,0 +1,381 @@
+use super::ActivitySource;
+use crate::error::AppError;
+use crate::search::SearchOutcome;
+use crate::{Issue, IssueInvolvement, PullRequest, Repository, ReviewedPullRequest};
+
+/// Queries one source per GitHub host and merges the results, tagging every
+/// repository with the host it came from.
+///
+/// Each host's range is searched (and split around the result cap) on its own.
+/// With more than one host, owners are qualified as `host/owner` so the same
+/// organization name on two hosts stays apart; an owner that doesn't start with
+/// a configured host matches on every host.
+pub struct MultiHostSource {
+    sources: Vec<(String, Box<dyn ActivitySource>)>,
+}
+
+impl MultiHostSource {
+    pub fn new(sources: Vec<(String, Box<dyn ActivitySource>)>) -> Self {
+        Self { sources }
+    }
+
+    /// The sources to query for `owner`, each with the owner as that host knows it.
+    fn sources_for<'a>(
+        &'a self,
+        owner: Option<&'a str>,
+    ) -> impl Iterator<Item = (&'a String, &'a dyn ActivitySource, Option<&'a str>)> {
+        let qualified = owner.and_then(|owner| {
+            self.sources.iter().find_map(|(host, _)| {
+                let (prefix, rest) = owner.split_at_checked(host.len())?;
+                let owner = rest.strip_prefix('/')?;
+                prefix.eq_ignore_ascii_case(host).then_some((host, owner))
+            })
+        });
+        let (host, owner) = match qualified {
+            Some((host, owner)) => (Some(host), Some(owner)),
+            None => (None, owner),
+        };
+
+        self.sources
+            .iter()
+            .filter(move |(h, _)| host.is_none_or(|host| h == host))
+            .map(move |(h, source)| (h, source.as_ref(), owner))
+    }
+
+    /// Runs `search` against every source in turn, tagging what it returns with the host.
+    fn merge<'a, T>(
+        sources: impl Iterator<Item = (&'a String, &'a dyn ActivitySource, Option<&'a str>)>,
+        mut search: impl FnMut(&dyn ActivitySource, Option<&str>) -> Result<Vec<T>, AppError>,
+        repository: fn(&mut T) -> &mut Repository,
+    ) -> Result<Vec<T>, AppError> {
+        let mut merged = Vec::new();
+
+        for (host, source, owner) in sources {
+            let items = search(source, owner).map_err(|e| e.with_host(host))?;
+            merged.extend(items.into_iter().map(|mut item| {
+                repository(&mut item).host = Some(host.clone());
+                item
+            }));
+        }
+
+        Ok(merged)
+    }
+
+    fn merge_outcomes<'a, T>(
+        sources: impl Iterator<Item = (&'a String, &'a dyn ActivitySource, Option<&'a str>)>,
+        mut search: impl FnMut(&dyn ActivitySource, Option<&str>) -> Result<SearchOutcome<T>, AppError>,
+        repository: fn(&mut T) -> &mut Repository,
+    ) -> Result<SearchOutcome<T>, AppError> {
+        let mut complete = true;
+        let items = Self::merge(
+            sources,
+            |source, owner| {
+                let outcome = search(source, owner)?;
+                complete &= outcome.complete;
+                Ok(outcome.items)
+            },
+            repository,
+        )?;
+
+        Ok(SearchOutcome { items, complete })
+    }
+}
+
+impl ActivitySource for MultiHostSource {
+    fn authored_prs(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        Self::merge(
+            self.sources_for(owner),
+            |source, owner| source.authored_prs(date_range, owner),
+            |pr| &mut pr.repository,
+        )
+    }
+
+    fn reviewed_prs(&self, date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+        Self::merge(
+            self.sources_for(None),
+            |source, _| source.reviewed_prs(date_range),
+            |pr| &mut pr.repository,
+        )
+    }
+
+    fn issues(
+        &self,
+        date_range: &str,
+        owner: Option<&str>,
+        involvement: IssueInvolvement,
+    ) -> Result<Vec<Issue>, AppError> {
+        Self::merge(
+            self.sources_for(owner),
+            |source, owner| source.issues(date_range, owner, involvement),
+            |issue| &mut issue.repository,
+        )
+    }
+
+    fn as_of(&self) -> Option<String> {
+        self.sources
+            .iter()
+            .filter_map(|(_, source)| source.as_of())
+            .min()
+    }
+
+    fn search_authored(
+        &self,
+        start: &str,
+        end: &str,
+        owner: Option<&str>,
+    ) -> Result<SearchOutcome<PullRequest>, AppError> {
+        Self::merge_outcomes(
+            self.sources_for(owner),
+            |source, owner| source.search_authored(start, end, owner),
+            |pr| &mut pr.repository,
+        )
+    }
+
+    fn search_reviewed(
+        &self,
+        start: &str,
+        end: &str,
+    ) -> Result<SearchOutcome<ReviewedPullRequest>, AppError> {
+        Self::merge_outcomes(
+            self.sources_for(None),
+            |source, _| source.search_reviewed(start, end),
+            |pr| &mut pr.repository,
+        )
+    }
+
+    fn search_issues(
+        &self,
+        start: &str,
+        end: &str,
+        owner: Option<&str>,
+        involvement: IssueInvolvement,
+    ) -> Result<SearchOutcome<Issue>, AppError> {
+        Self::merge_outcomes(
+            self.sources_for(owner),
+            |source, owner| source.search_issues(start, end, owner, involvement),
+            |issue| &mut issue.repository,
+        )
+    }
+
+    fn search_owners(&self, start: &str, end: &str) -> Result<SearchOutcome<String>, AppError> {
+        let qualify = self.sources.len() > 1;
+        let mut items = Vec::new();
+        let mut complete = true;
+
+        for (host, source) in &self.sources {
+            let outcome = source
+                .search_owners(start, end)
+                .map_err(|e| e.with_host(host))?;
+            complete &= outcome.complete;
+            items.extend(outcome.items.into_iter().map(|owner| {
+                if qualify {
+                    format!("{}/{}", host, owner)
+                } else {
+                    owner
+                }
+            }));
+        }
+
+        Ok(SearchOutcome { items, complete })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Repository;
+    use crate::error::ErrorCode;
+    use crate::sources::owned_by;
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    struct SingleRepoSource(&'static str);
+
+    impl ActivitySource for SingleRepoSource {
+        fn authored_prs(
+            &self,
+            _date_range: &str,
+            _owner: Option<&str>,
+        ) -> Result<Vec<PullRequest>, AppError> {
+            Ok(vec![PullRequest {
+                title: "Change".to_string(),
+                url: format!("https://example.com/{}/pull/1", self.0),
+                body: None,
+                closed_at: "2024-11-15T10:00:00Z".to_string(),
+                created_at: None,
+                number: Some(1),
+                repository: Repository {
+                    name: "repo".to_string(),
+                    name_with_owner: self.0.to_string(),
+                    host: None,
+                },
+                size: None,
+                labels: Vec::new(),
+                milestone: None,
+                closing_issues: Vec::new(),
+            }])
+        }
+
+        fn reviewed_prs(&self, _date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+            Err(AppError::new(
+                ErrorCode::NotAuthenticated,
+                "not authenticated",
+            ))
+        }
+    }
+
+    fn two_hosts() -> MultiHostSource {
+        MultiHostSource::new(vec![
+            (
+                "github.com".to_string(),
+                Box::new(SingleRepoSource("oss/repo")),
+            ),
+            (
+                "github.acme.com".to_string(),
+                Box::new(SingleRepoSource("acme/repo")),
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_merges_and_tags_hosts() {
+        let prs = two_hosts()
+            .authored_prs("2024-01-01..2024-12-31", None)
+            .unwrap();
+
+        assert_eq!(prs.len(), 2);
+        assert_eq!(prs[0].repository.host.as_deref(), Some("github.com"));
+        assert_eq!(prs[1].repository.host.as_deref(), Some("github.acme.com"));
+    }
+
+    struct WindowSource {
+        repo: &'static str,
+        per_window: usize,
+        windows: Rc<RefCell<Vec<String>>>,
+    }
+
+    impl ActivitySource for WindowSource {
+        fn authored_prs(
+            &self,
+            date_range: &str,
+            owner: Option<&str>,
+        ) -> Result<Vec<PullRequest>, AppError> {
+            self.windows
+                .borrow_mut()
+                .push(format!("{} {:?}", date_range, owner));
+            let pr = SingleRepoSource(self.repo).authored_prs(date_range, owner)?;
+            Ok(pr.into_iter().cycle().take(self.per_window).collect())
+        }
+
+        fn reviewed_prs(&self, _date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+            Ok(Vec::new())
+        }
+    }
+
+    fn window_hosts(per_window: usize) -> (MultiHostSource, Rc<RefCell<Vec<String>>>) {
+        let windows = Rc::new(RefCell::new(Vec::new()));
+        let source = |repo| {
+            Box::new(WindowSource {
+                repo,
+                per_window,
+                windows: windows.clone(),
+            }) as Box<dyn ActivitySource>
+        };
+        let multi = MultiHostSource::new(vec![
+            ("github.com".to_string(), source("acme/oss")),
+            ("github.acme.com".to_string(), source("acme/internal")),
+        ]);
+        (multi, windows)
+    }
+
+    #[test]
+    fn test_applies_search_cap_per_host() {
+        let (source, windows) = window_hosts(600);
+
+        let outcome = source
+            .search_authored("2024-01-01", "2024-12-31", None)
+            .unwrap();
+
+        assert_eq!(outcome.items.len(), 1200);
+        assert!(outcome.complete);
+        assert_eq!(windows.borrow().len(), 2);
+    }
+
+    #[test]
+    fn test_qualifies_owners_by_host() {
+        let (source, windows) = window_hosts(1);
+
+        let owners = source.search_owners("2024-01-01", "2024-12-31").unwrap();
+        windows.borrow_mut().clear();
+        let prs = source
+            .search_authored("2024-01-01", "2024-12-31", Some("github.acme.com/acme"))
+            .unwrap();
+
+        assert_eq!(
+            owners.items,
+            vec!["github.com/acme", "github.acme.com/acme"]
+        );
+        assert_eq!(
+            *windows.borrow(),
+            vec!["2024-01-01..2024-12-31 Some(\"acme\")"]
+        );
+        assert_eq!(
+            prs.items[0].repository.host.as_deref(),
+            Some("github.acme.com")
+        );
+    }
+
+    #[test]
+    fn test_tags_errors_with_host() {
+        let error = two_hosts()
+            .reviewed_prs("2024-01-01..2024-12-31")
+            .unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::NotAuthenticated);
+        assert_eq!(error.host.as_deref(), Some("github.com"));
+        assert_eq!(error.to_string(), "github.com: not authenticated");
+    }
+
+    #[test]
+    fn test_qualifies_nested_groups_by_their_top_level_group() {
+        let windows = Rc::new(RefCell::new(Vec::new()));
+        let source = |repo| {
+            Box::new(WindowSource {
+                repo,
+                per_window: 1,
+                windows: windows.clone(),
+            }) as Box<dyn ActivitySource>
+        };
+        let multi = MultiHostSource::new(vec![
+            ("github.com".to_string(), source("acme/oss")),
+            (
+                "gitlab.acme.com".to_string(),
+                source("platform/infra/cache"),
+            ),
+        ]);
+
+        let owners = multi.search_owners("2024-01-01", "2024-12-31").unwrap();
+        windows.borrow_mut().clear();
+        let prs = multi
+            .search_authored("2024-01-01", "2024-12-31", Some("gitlab.acme.com/platform"))
+            .unwrap();
+
+        assert_eq!(
+            owners.items,
+            vec!["github.com/acme", "gitlab.acme.com/platform"]
+        );
+        assert_eq!(
+            *windows.borrow(),
+            vec!["2024-01-01..2024-12-31 Some(\"platform\")"]
+        );
+        assert_eq!(
+            prs.items[0].repository.name_with_owner,
+            "platform/infra/cache"
+        );
+        assert!(owned_by(&prs.items[0].repository, Some("platform")));
+    }
+}
@@ -0,0 +1,193 @@
+use super::pr_lookup;
+use crate::error::AppError;
+use crate::{IssueReference, PullRequest, PullRequestSize};
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+const PR_DETAIL_FIELDS: &str = "additions deletions changedFiles commits { totalCount } \
+     labels(first: 20) { nodes { name } } milestone { title } \
+     closingIssuesReferences(first: 10) { nodes { number title url } }";
+
+/// The GraphQL shape of [`PR_DETAIL_FIELDS`].
+#[derive(Debug, Deserialize)]
+struct DetailNode {
+    additions: u32,
+    deletions: u32,
+    #[serde(rename = "changedFiles")]
+    changed_files: u32,
+    commits: TotalCount,
+    labels: Option<Connection<LabelNode>>,
+    milestone: Option<MilestoneNode>,
+    #[serde(rename = "closingIssuesReferences")]
+    closing_issues_references: Option<Connection<IssueReference>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TotalCount {
+    #[serde(rename = "totalCount")]
+    total_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct Connection<T> {
+    nodes: Vec<Option<T>>,
+}
+
+impl<T> Connection<T> {
+    fn into_nodes(self) -> impl Iterator<Item = T> {
+        self.nodes.into_iter().flatten()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct LabelNode {
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MilestoneNode {
+    title: String,
+}
+
+impl DetailNode {
+    fn apply_to(self, pr: &mut PullRequest) {
+        pr.size = Some(PullRequestSize {
+            additions: self.additions,
+            deletions: self.deletions,
+            changed_files: self.changed_files,
+            commits: self.commits.total_count,
+        });
+        pr.labels = self
+            .labels
+            .map(|labels| labels.into_nodes().map(|label| label.name).collect())
+            .unwrap_or_default();
+        pr.milestone = self.milestone.map(|milestone| milestone.title);
+        pr.closing_issues = self
+            .closing_issues_references
+            .map(|issues| issues.into_nodes().collect())
+            .unwrap_or_default();
+    }
+}
+
+/// Looks up size, labels, milestone and closing issues for each pull request and fills them in.
+/// Pull requests that can't be looked up keep them as `None`; only cancellation is an error.
+/// Returns whether every pull request could be looked up.
+///
+/// `run` executes a GraphQL query against the pull requests' host and returns its `data` object.
+pub fn enrich<F>(prs: &mut [PullRequest], run: F) -> Result<bool, AppError>
+where
+    F: Fn(&str) -> Result<Map<String, Value>, AppError>,
+{
+    let urls: Vec<&str> = prs.iter().map(|pr| pr.url.as_str()).collect();
+    let lookup = pr_lookup::lookup(&urls, PR_DETAIL_FIELDS, run)?;
+
+    for (pr, node) in prs.iter_mut().zip(lookup.nodes) {
+        if let Ok(details) = serde_json::from_value::<DetailNode>(node) {
+            details.apply_to(pr);
+        }
+    }
+
+    Ok(lookup.skipped == 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Repository;
+    use crate::error::ErrorCode;
+    use serde_json::json;
+
+    #[test]
+    fn test_enrich_fills_in_details() {
+        let mut prs = vec![PullRequest {
+            title: "Migrate billing".to_string(),
+            url: "https://github.com/org/repo/pull/9".to_string(),
+            body: None,
+            closed_at: "2024-11-15T10:00:00Z".to_string(),
+            created_at: None,
+            number: Some(9),
+            repository: Repository {
+                name: "repo".to_string(),
+                name_with_owner: "org/repo".to_string(),
+                host: None,
+            },
+            size: None,
+            labels: Vec::new(),
+            milestone: None,
+            closing_issues: Vec::new(),
+        }];
+
+        enrich(&mut prs, |_| {
+            let data = json!({
+                "viewer": { "login": "me" },
+                "pr0": {
+                    "additions": 120,
+                    "deletions": 40,
+                    "changedFiles": 7,
+                    "commits": { "totalCount": 3 },
+                    "labels": { "nodes": [{ "name": "perf" }, { "name": "incident" }] },
+                    "milestone": { "title": "Q4" },
+                    "closingIssuesReferences": { "nodes": [
+                        { "number": 123, "title": "Checkout is slow", "url": "https://github.com/org/repo/issues/123" }
+                    ] }
+                }
+            });
+            Ok(data.as_object().unwrap().clone())
+        })
+        .unwrap();
+
+        let pr = &prs[0];
+        assert_eq!(
+            pr.size,
+            Some(PullRequestSize {
+                additions: 120,
+                deletions: 40,
+                changed_files: 7,
+                commits: 3,
+            })
+        );
+        assert_eq!(pr.labels, vec!["perf", "incident"]);
+        assert_eq!(pr.milestone.as_deref(), Some("Q4"));
+        assert_eq!(pr.closing_issues[0].number, 123);
+        assert_eq!(pr.closing_issues[0].title, "Checkout is slow");
+    }
+
+    #[test]
+    fn test_enrich_leaves_missing_pull_requests_alone() {
+        let mut prs = vec![PullRequest {
+            title: "Deleted repo".to_string(),
+            url: "https://github.com/org/gone/pull/1".to_string(),
+            body: None,
+            closed_at: "2024-11-15T10:00:00Z".to_string(),
+            created_at: None,
+            number: Some(1),
+            repository: Repository {
+                name: "gone".to_string(),
+                name_with_owner: "org/gone".to_string(),
+                host: None,
+            },
+            size: None,
+            labels: Vec::new(),
+            milestone: None,
+            closing_issues: Vec::new(),
+        }];
+
+        let looked_up = enrich(&mut prs, |_| {
+            let data = json!({ "viewer": { "login": "me" }, "pr0": null });
+            Ok(data.as_object().unwrap().clone())
+        })
+        .unwrap();
+        let failed = enrich(&mut prs, |_| {
+            Err(AppError::new(
+                ErrorCode::Api,
+                "Field 'closingIssuesReferences' doesn't exist",
+            ))
+        })
+        .unwrap();
+
+        assert!(looked_up);
+        assert!(!failed);
+        assert!(prs[0].size.is_none());
+        assert!(prs[0].labels.is_empty());
+    }
+}
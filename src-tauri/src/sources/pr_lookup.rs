@@ -0,0 +1,157 @@
+use crate::cancel;
+use crate::error::AppError;
+use crate::progress::{self, FetchPhase};
+use serde_json::{Map, Value};
+
+/// How many pull requests are looked up per GraphQL request.
+const BATCH_SIZE: usize = 25;
+
+/// The result of looking up fields on a list of pull requests by URL.
+#[derive(Debug, Default)]
+pub struct PullRequestLookup {
+    /// Login of the user the query ran as.
+    pub viewer: Option<String>,
+    /// One node per URL, in order; `Value::Null` when the pull request wasn't
+    /// found or its batch couldn't be looked up.
+    pub nodes: Vec<Value>,
+    /// Pull requests left out because their batch couldn't be looked up.
+    pub skipped: usize,
+}
+
+/// Builds one GraphQL query that selects `fields` on each URL, aliased `pr0`, `pr1`, ...
+fn batch_query(urls: &[&str], fields: &str) -> String {
+    let lookups: Vec<String> = urls
+        .iter()
+        .enumerate()
+        .map(|(i, url)| {
+            format!(
+                "pr{}: resource(url: {}) {{ ... on PullRequest {{ {} }} }}",
+                i,
+                Value::String(url.to_string()),
+                fields
+            )
+        })
+        .collect();
+
+    format!("query {{ viewer {{ login }} {} }}", lookups.join(" "))
+}
+
+fn viewer_login(data: &Map<String, Value>) -> Option<String> {
+    data.get("viewer")
+        .and_then(|v| v.get("login"))
+        .and_then(|l| l.as_str())
+        .map(|l| l.to_string())
+}
+
+/// Login of the user GraphQL queries run as, through the same `run` as [`lookup`].
+pub fn viewer<F>(run: F) -> Result<Option<String>, AppError>
+where
+    F: Fn(&str) -> Result<Map<String, Value>, AppError>,
+{
+    run("query { viewer { login } }").map(|data| viewer_login(&data))
+}
+
+/// Selects `fields` on every pull request in `urls`, a batch of pull requests per query.
+///
+/// `run` executes a GraphQL query against the pull requests' host and returns its `data` object.
+/// The lookup is best effort: a batch that fails (an inaccessible pull request, a field
+/// the host doesn't support) is left as `Value::Null` nodes and counted in `skipped`.
+/// Only cancellation is returned as an error.
+pub fn lookup<F>(urls: &[&str], fields: &str, run: F) -> Result<PullRequestLookup, AppError>
+where
+    F: Fn(&str) -> Result<Map<String, Value>, AppError>,
+{
+    let mut result = PullRequestLookup::default();
+    if !urls.is_empty() {
+        progress::set_phase(FetchPhase::Enriching);
+    }
+
+    for batch in urls.chunks(BATCH_SIZE) {
+        cancel::check()?;
+        let mut data = match run(&batch_query(batch, fields)) {
+            Ok(data) => data,
+            Err(_) => {
+                result.nodes.extend(batch.iter().map(|_| Value::Null));
+                result.skipped += batch.len();
+                continue;
+            }
+        };
+
+        if result.viewer.is_none() {
+            result.viewer = viewer_login(&data);
+        }
+        result
+            .nodes
+            .extend((0..batch.len()).map(|i| data.remove(&format!("pr{}", i)).unwrap_or_default()));
+    }
+
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorCode;
+    use serde_json::json;
+    use std::cell::Cell;
+
+    #[test]
+    fn test_batch_query_aliases_urls() {
+        let query = batch_query(
+            &[
+                "https://github.com/a/b/pull/1",
+                "https://github.com/a/b/pull/2",
+            ],
+            "additions",
+        );
+
+        assert!(query.starts_with("query { viewer { login }"));
+        assert!(query.contains(
+            r#"pr0: resource(url: "https://github.com/a/b/pull/1") { ... on PullRequest { additions } }"#
+        ));
+        assert!(query.contains(r#"pr1: resource(url: "https://github.com/a/b/pull/2")"#));
+    }
+
+    #[test]
+    fn test_lookup_batches_queries_and_keeps_order() {
+        let urls = vec!["https://github.com/a/b/pull/1"; BATCH_SIZE + 1];
+        let queries = Cell::new(0);
+
+        let result = lookup(&urls, "additions", |_| {
+            queries.set(queries.get() + 1);
+            let data =
+                json!({ "viewer": { "login": "me" }, "pr0": { "additions": queries.get() } });
+            Ok(data.as_object().unwrap().clone())
+        })
+        .unwrap();
+
+        assert_eq!(queries.get(), 2);
+        assert_eq!(result.viewer.as_deref(), Some("me"));
+        assert_eq!(result.nodes.len(), BATCH_SIZE + 1);
+        assert_eq!(result.nodes[0]["additions"], 1);
+        assert!(result.nodes[1].is_null());
+        assert_eq!(result.nodes[BATCH_SIZE]["additions"], 2);
+    }
+
+    #[test]
+    fn test_lookup_skips_failed_batches() {
+        let urls = vec!["https://github.com/a/b/pull/1"; BATCH_SIZE + 1];
+        let queries = Cell::new(0);
+
+        let result = lookup(&urls, "additions", |_| {
+            queries.set(queries.get() + 1);
+            if queries.get() == 1 {
+                return Err(AppError::new(ErrorCode::Api, "Could not resolve to a node"));
+            }
+            let data = json!({ "viewer": { "login": "me" }, "pr0": { "additions": 3 } });
+            Ok(data.as_object().unwrap().clone())
+        })
+        .unwrap();
+
+        assert_eq!(result.viewer.as_deref(), Some("me"));
+        assert_eq!(result.nodes.len(), BATCH_SIZE + 1);
+        assert!(result.nodes[0].is_null());
+        assert_eq!(result.skipped, BATCH_SIZE);
+        assert_eq!(result.nodes[BATCH_SIZE]["additions"], 3);
+    }
+}
@@ -0,0 +1,214 @@
+use super::pr_lookup;
+use crate::error::AppError;
+use crate::{ReviewDetails, ReviewState, ReviewedPullRequest};
+use chrono::DateTime;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+
+const REVIEW_ACTIVITY_FIELDS: &str = "reviews(first: 100) { nodes { author { login } state submittedAt comments { totalCount } } } \
+     timelineItems(itemTypes: [REVIEW_REQUESTED_EVENT], first: 100) { nodes { ... on ReviewRequestedEvent { createdAt requestedReviewer { ... on User { login } } } } }";
+
+#[derive(Debug, Deserialize)]
+struct Connection<T> {
+    nodes: Vec<Option<T>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Login {
+    login: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewNode {
+    author: Option<Login>,
+    state: String,
+    #[serde(rename = "submittedAt")]
+    submitted_at: Option<String>,
+    comments: TotalCount,
+}
+
+#[derive(Debug, Deserialize)]
+struct TotalCount {
+    #[serde(rename = "totalCount")]
+    total_count: u32,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewRequestNode {
+    #[serde(rename = "createdAt")]
+    created_at: Option<String>,
+    #[serde(rename = "requestedReviewer")]
+    requested_reviewer: Option<Login>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ReviewActivity {
+    reviews: Connection<ReviewNode>,
+    #[serde(rename = "timelineItems")]
+    timeline_items: Connection<ReviewRequestNode>,
+}
+
+fn parse_state(state: &str) -> Option<ReviewState> {
+    match state {
+        "APPROVED" => Some(ReviewState::Approved),
+        "CHANGES_REQUESTED" => Some(ReviewState::ChangesRequested),
+        "COMMENTED" => Some(ReviewState::Commented),
+        "DISMISSED" => Some(ReviewState::Dismissed),
+        _ => None,
+    }
+}
+
+fn minutes_between(from: &str, to: &str) -> Option<i64> {
+    let from = DateTime::parse_from_rfc3339(from).ok()?;
+    let to = DateTime::parse_from_rfc3339(to).ok()?;
+    Some((to - from).num_minutes())
+}
+
+impl ReviewActivity {
+    /// Summarizes the reviews `login` left, or `None` if they left none.
+    fn details_for(&self, login: &str) -> Option<ReviewDetails> {
+        let is_viewer = |author: &Option<Login>| {
+            author
+                .as_ref()
+                .is_some_and(|a| a.login.eq_ignore_ascii_case(login))
+        };
+
+        let mut reviews: Vec<&ReviewNode> = self
+            .reviews
+            .nodes
+            .iter()
+            .flatten()
+            .filter(|r| is_viewer(&r.author) && r.submitted_at.is_some())
+            .collect();
+        reviews.sort_by(|a, b| a.submitted_at.cmp(&b.submitted_at));
+
+        let last = reviews.last()?;
+        let requested_at = self
+            .timeline_items
+            .nodes
+            .iter()
+            .flatten()
+            .filter(|event| is_viewer(&event.requested_reviewer))
+            .filter_map(|event| event.created_at.clone())
+            .min();
+        let first_review_at = requested_at.as_ref().and_then(|requested_at| {
+            reviews
+                .iter()
+                .filter_map(|r| r.submitted_at.as_ref())
+                .find(|submitted_at| *submitted_at >= requested_at)
+        });
+
+        Some(ReviewDetails {
+            comment_count: reviews.iter().map(|r| r.comments.total_count).sum(),
+            state: parse_state(&last.state),
+            requested_at: requested_at.clone(),
+            first_reviewed_at: reviews[0].submitted_at.clone(),
+            minutes_to_first_review: match (&requested_at, first_review_at) {
+                (Some(requested_at), Some(first_review_at)) => {
+                    minutes_between(requested_at, first_review_at)
+                }
+                _ => None,
+            },
+        })
+    }
+}
+
+/// Looks up the user's review activity for each pull request and fills in `review`.
+/// Pull requests that can't be looked up keep `None`; only cancellation is an error.
+/// Returns whether every pull request could be looked up.
+///
+/// `run` executes a GraphQL query against the pull requests' host and returns its `data` object.
+pub fn enrich<F>(prs: &mut [ReviewedPullRequest], run: F) -> Result<bool, AppError>
+where
+    F: Fn(&str) -> Result<Map<String, Value>, AppError>,
+{
+    let urls: Vec<&str> = prs.iter().map(|pr| pr.url.as_str()).collect();
+    let lookup = pr_lookup::lookup(&urls, REVIEW_ACTIVITY_FIELDS, run)?;
+    let complete = lookup.skipped == 0;
+    let Some(login) = lookup.viewer else {
+        return Ok(complete);
+    };
+
+    for (pr, node) in prs.iter_mut().zip(lookup.nodes) {
+        pr.review = serde_json::from_value::<ReviewActivity>(node)
+            .ok()
+            .and_then(|activity| activity.details_for(&login));
+    }
+
+    Ok(complete)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Author, Repository};
+
+    fn reviewed_pr() -> ReviewedPullRequest {
+        ReviewedPullRequest {
+            title: "Refactor auth".to_string(),
+            url: "https://github.com/org/repo/pull/3".to_string(),
+            closed_at: None,
+            created_at: "2024-11-13T09:00:00Z".to_string(),
+            author: Author {
+                login: "teammate".to_string(),
+            },
+            repository: Repository {
+                name: "repo".to_string(),
+                name_with_owner: "org/repo".to_string(),
+                host: None,
+            },
+            review: None,
+        }
+    }
+
+    #[test]
+    fn test_enrich_summarizes_viewer_reviews() {
+        let data: Map<String, Value> = serde_json::from_str(
+            r#"{
+                "viewer": {"login": "me"},
+                "pr0": {
+                    "reviews": {"nodes": [
+                        {"author": {"login": "me"}, "state": "CHANGES_REQUESTED", "submittedAt": "2024-11-13T12:30:00Z", "comments": {"totalCount": 4}},
+                        {"author": {"login": "other"}, "state": "APPROVED", "submittedAt": "2024-11-13T13:00:00Z", "comments": {"totalCount": 1}},
+                        {"author": {"login": "me"}, "state": "APPROVED", "submittedAt": "2024-11-14T09:00:00Z", "comments": {"totalCount": 1}}
+                    ]},
+                    "timelineItems": {"nodes": [
+                        {"createdAt": "2024-11-13T10:00:00Z", "requestedReviewer": {"login": "me"}},
+                        {},
+                        {"createdAt": "2024-11-13T09:30:00Z", "requestedReviewer": {"login": "other"}}
+                    ]}
+                }
+            }"#,
+        )
+        .unwrap();
+
+        let mut prs = vec![reviewed_pr()];
+        enrich(&mut prs, |_| Ok(data.clone())).unwrap();
+        let review = prs[0].review.as_ref().unwrap();
+
+        assert_eq!(review.comment_count, 5);
+        assert_eq!(review.state, Some(ReviewState::Approved));
+        assert_eq!(review.requested_at.as_deref(), Some("2024-11-13T10:00:00Z"));
+        assert_eq!(
+            review.first_reviewed_at.as_deref(),
+            Some("2024-11-13T12:30:00Z")
+        );
+        assert_eq!(review.minutes_to_first_review, Some(150));
+    }
+
+    #[test]
+    fn test_enrich_without_own_reviews() {
+        let data: Map<String, Value> = serde_json::from_str(
+            r#"{
+                "viewer": {"login": "me"},
+                "pr0": {"reviews": {"nodes": []}, "timelineItems": {"nodes": []}}
+            }"#,
+        )
+        .unwrap();
+
+        let mut prs = vec![reviewed_pr()];
+        enrich(&mut prs, |_| Ok(data.clone())).unwrap();
+
+        assert!(prs[0].review.is_none());
+    }
+}
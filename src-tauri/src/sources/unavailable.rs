@@ -0,0 +1,47 @@
+use super::ActivitySource;
+use crate::error::AppError;
+use crate::{Issue, IssueInvolvement, PullRequest, ReviewedPullRequest};
+
+/// Stands in for a host that couldn't be set up (no token, `gh` missing), failing
+/// every fetch with the setup error. Wrapped in a [`CachedSource`](super::CachedSource),
+/// it lets whatever was synced for the host still be served offline.
+pub struct UnavailableSource {
+    error: AppError,
+}
+
+impl UnavailableSource {
+    pub fn new(error: AppError) -> Self {
+        Self { error }
+    }
+}
+
+impl ActivitySource for UnavailableSource {
+    fn authored_prs(
+        &self,
+        _date_range: &str,
+        _owner: Option<&str>,
+    ) -> Result<Vec<PullRequest>, AppError> {
+        Err(self.error.clone())
+    }
+
+    fn reviewed_prs(&self, _date_range: &str) -> Result<Vec<ReviewedPullRequest>, AppError> {
+        Err(self.error.clone())
+    }
+
+    fn issues(
+        &self,
+        _date_range: &str,
+        _owner: Option<&str>,
+        _involvement: IssueInvolvement,
+    ) -> Result<Vec<Issue>, AppError> {
+        Err(self.error.clone())
+    }
+
+    fn login(&self) -> Result<Option<String>, AppError> {
+        Err(self.error.clone())
+    }
+
+    fn authored_repositories(&self, _date_range: &str) -> Result<Vec<String>, AppError> {
+        Err(self.error.clone())
+    }
+}
@@ -0,0 +1,64 @@
+/// Incremental parser for `text/event-stream` responses.
+///
+/// Bytes are fed in as they arrive, which may split lines and even UTF-8
+/// characters; only the `data` of complete events is returned.
+#[derive(Debug, Default)]
+pub struct SseParser {
+    buffer: Vec<u8>,
+    data: Vec<String>,
+}
+
+impl SseParser {
+    /// Feeds `bytes` to the parser and returns the data of every event they complete.
+    pub fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut events = Vec::new();
+
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            let line = line.trim_end_matches(['\n', '\r']);
+
+            if line.is_empty() {
+                if !self.data.is_empty() {
+                    events.push(self.data.join("\n"));
+                    self.data.clear();
+                }
+            } else if let Some(value) = line.strip_prefix("data:") {
+                self.data
+                    .push(value.strip_prefix(' ').unwrap_or(value).to_string());
+            }
+        }
+
+        events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_events_split_across_chunks() {
+        let mut parser = SseParser::default();
+
+        assert!(parser.push(b"data: {\"a\"").is_empty());
+        assert_eq!(
+            parser.push(b":1}\n\n: keep-alive\n\ndata: [DONE]\n"),
+            vec!["{\"a\":1}"]
+        );
+        assert_eq!(parser.push(b"\n"), vec!["[DONE]"]);
+    }
+
+    #[test]
+    fn test_handles_crlf_split_characters_and_multiline_data() {
+        let mut parser = SseParser::default();
+        let text = "data: caf\u{e9}\r\ndata: ol\u{e9}\r\n\r\n".as_bytes();
+        let (first, second) = text.split_at(10);
+
+        let mut events = parser.push(first);
+        events.extend(parser.push(second));
+
+        assert_eq!(events, vec!["caf\u{e9}\nol\u{e9}"]);
+    }
+}
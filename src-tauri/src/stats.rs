@@ -0,0 +1,238 @@
+use crate::{PullRequest, PullRequestSize};
+use serde::{Deserialize, Serialize};
+
+/// Lines changed (additions plus deletions) below which a pull request counts as small.
+const SMALL_PR_LINES: u32 = 50;
+/// Lines changed from which a pull request counts as large.
+const LARGE_PR_LINES: u32 = 500;
+
+/// Rough size bucket of a pull request, by lines changed.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SizeCategory {
+    Small,
+    Medium,
+    Large,
+}
+
+impl SizeCategory {
+    pub fn describe(self) -> &'static str {
+        match self {
+            SizeCategory::Small => "small",
+            SizeCategory::Medium => "medium",
+            SizeCategory::Large => "large",
+        }
+    }
+}
+
+impl PullRequestSize {
+    pub fn lines_changed(&self) -> u32 {
+        self.additions + self.deletions
+    }
+
+    pub fn category(&self) -> SizeCategory {
+        match self.lines_changed() {
+            lines if lines < SMALL_PR_LINES => SizeCategory::Small,
+            lines if lines < LARGE_PR_LINES => SizeCategory::Medium,
+            _ => SizeCategory::Large,
+        }
+    }
+}
+
+/// Aggregate size metrics over the pull requests whose size is known.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct SizeStats {
+    /// Pull requests the totals cover; sources that can't measure size are left out.
+    #[serde(rename = "measuredPrs")]
+    pub measured_prs: usize,
+    pub additions: u64,
+    pub deletions: u64,
+    #[serde(rename = "changedFiles")]
+    pub changed_files: u64,
+    pub commits: u64,
+    #[serde(rename = "medianLinesChanged")]
+    pub median_lines_changed: u32,
+    pub small: usize,
+    pub medium: usize,
+    pub large: usize,
+}
+
+pub fn size_stats(prs: &[PullRequest]) -> SizeStats {
+    let sizes: Vec<&PullRequestSize> = prs.iter().filter_map(|pr| pr.size.as_ref()).collect();
+    let mut stats = SizeStats {
+        measured_prs: sizes.len(),
+        ..SizeStats::default()
+    };
+
+    for size in &sizes {
+        stats.additions += u64::from(size.additions);
+        stats.deletions += u64::from(size.deletions);
+        stats.changed_files += u64::from(size.changed_files);
+        stats.commits += u64::from(size.commits);
+
+        match size.category() {
+            SizeCategory::Small => stats.small += 1,
+            SizeCategory::Medium => stats.medium += 1,
+            SizeCategory::Large => stats.large += 1,
+        }
+    }
+
+    let mut lines: Vec<u32> = sizes.iter().map(|s| s.lines_changed()).collect();
+    lines.sort_unstable();
+    stats.median_lines_changed = match lines.len() {
+        0 => 0,
+        n if n % 2 == 1 => lines[n / 2],
+        n => (lines[n / 2 - 1] + lines[n / 2]) / 2,
+    };
+
+    stats
+}
+
+/// Pull requests sharing one label or milestone.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LabelCount {
+    pub name: String,
+    pub count: usize,
+    /// URLs of the pull requests, in the order they were fetched.
+    #[serde(rename = "prUrls")]
+    pub pr_urls: Vec<String>,
+}
+
+/// Pull requests grouped by label and by milestone, most common first.
+/// Pull requests without labels or a milestone are left out.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct LabelStats {
+    pub labels: Vec<LabelCount>,
+    pub milestones: Vec<LabelCount>,
+}
+
+fn count_by<'a>(
+    prs: &'a [PullRequest],
+    names: impl Fn(&'a PullRequest) -> Vec<&'a str>,
+) -> Vec<LabelCount> {
+    let mut counts: Vec<LabelCount> = Vec::new();
+
+    for pr in prs {
+        for name in names(pr) {
+            match counts.iter_mut().find(|c| c.name == name) {
+                Some(count) => {
+                    count.count += 1;
+                    count.pr_urls.push(pr.url.clone());
+                }
+                None => counts.push(LabelCount {
+                    name: name.to_string(),
+                    count: 1,
+                    pr_urls: vec![pr.url.clone()],
+                }),
+            }
+        }
+    }
+
+    counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.name.cmp(&b.name)));
+    counts
+}
+
+pub fn label_stats(prs: &[PullRequest]) -> LabelStats {
+    LabelStats {
+        labels: count_by(prs, |pr| pr.labels.iter().map(String::as_str).collect()),
+        milestones: count_by(prs, |pr| pr.milestone.as_deref().into_iter().collect()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Repository;
+
+    fn pr_with_size(additions: u32, deletions: u32) -> PullRequest {
+        PullRequest {
+            title: "Change".to_string(),
+            url: "https://github.com/org/repo/pull/1".to_string(),
+            body: None,
+            closed_at: "2024-11-15T10:00:00Z".to_string(),
+            created_at: None,
+            number: Some(1),
+            repository: Repository {
+                name: "repo".to_string(),
+                name_with_owner: "org/repo".to_string(),
+                host: None,
+            },
+            size: Some(PullRequestSize {
+                additions,
+                deletions,
+                changed_files: 2,
+                commits: 1,
+            }),
+            labels: Vec::new(),
+            milestone: None,
+            closing_issues: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_size_category() {
+        assert_eq!(
+            pr_with_size(10, 5).size.unwrap().category(),
+            SizeCategory::Small
+        );
+        assert_eq!(
+            pr_with_size(200, 100).size.unwrap().category(),
+            SizeCategory::Medium
+        );
+        assert_eq!(
+            pr_with_size(900, 300).size.unwrap().category(),
+            SizeCategory::Large
+        );
+    }
+
+    #[test]
+    fn test_size_stats_skips_unmeasured_prs() {
+        let mut unmeasured = pr_with_size(0, 0);
+        unmeasured.size = None;
+
+        let stats = size_stats(&[
+            pr_with_size(10, 0),
+            pr_with_size(100, 20),
+            pr_with_size(1000, 500),
+            unmeasured,
+        ]);
+
+        assert_eq!(stats.measured_prs, 3);
+        assert_eq!(stats.additions, 1110);
+        assert_eq!(stats.deletions, 520);
+        assert_eq!(stats.changed_files, 6);
+        assert_eq!(stats.commits, 3);
+        assert_eq!(stats.median_lines_changed, 120);
+        assert_eq!((stats.small, stats.medium, stats.large), (1, 1, 1));
+    }
+
+    #[test]
+    fn test_size_stats_empty() {
+        assert_eq!(size_stats(&[]), SizeStats::default());
+    }
+
+    #[test]
+    fn test_label_stats_groups_by_label_and_milestone() {
+        let mut first = pr_with_size(1, 1);
+        first.labels = vec!["bug".to_string(), "backend".to_string()];
+        first.milestone = Some("v2.0".to_string());
+        let mut second = pr_with_size(1, 1);
+        second.url = "https://github.com/org/repo/pull/2".to_string();
+        second.labels = vec!["bug".to_string()];
+
+        let stats = label_stats(&[first, second, pr_with_size(1, 1)]);
+
+        assert_eq!(stats.labels[0].name, "bug");
+        assert_eq!(stats.labels[0].count, 2);
+        assert_eq!(
+            stats.labels[0].pr_urls,
+            vec![
+                "https://github.com/org/repo/pull/1",
+                "https://github.com/org/repo/pull/2"
+            ]
+        );
+        assert_eq!(stats.labels[1].name, "backend");
+        assert_eq!(stats.milestones.len(), 1);
+        assert_eq!(stats.milestones[0].count, 1);
+    }
+}
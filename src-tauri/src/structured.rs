@@ -0,0 +1,239 @@
+use crate::PullRequest;
+use crate::error::{AppError, ErrorCode};
+use crate::language::Language;
+use crate::llm;
+use serde::{Deserialize, Serialize};
+
+/// Completion tokens for a structured summary, which spends some on JSON and cited URLs.
+pub const STRUCTURED_MAX_TOKENS: u32 = 3000;
+
+/// How `generate_ai_summary` writes the summary.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum SummaryFormat {
+    /// Free Markdown, streamed as it is written.
+    #[default]
+    Markdown,
+    /// Typed sections citing their PRs, returned once the whole reply is validated.
+    Json,
+}
+
+/// A statement in a structured summary, with the URLs of the PRs it is based on.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CitedPoint {
+    pub text: String,
+    #[serde(default, rename = "prUrls")]
+    pub pr_urls: Vec<String>,
+}
+
+/// The sections of the default template as typed fields.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StructuredSummary {
+    #[serde(rename = "impactSummary")]
+    pub impact_summary: CitedPoint,
+    pub achievements: Vec<CitedPoint>,
+    #[serde(default)]
+    pub growth: Vec<CitedPoint>,
+    /// Empty when the PRs show no collaboration or leadership.
+    #[serde(default)]
+    pub collaboration: Vec<CitedPoint>,
+    #[serde(default, rename = "talkingPoints")]
+    pub talking_points: Vec<CitedPoint>,
+}
+
+/// Appended to the rendered template, asking for JSON instead of Markdown.
+pub fn prompt_section(language: Language) -> String {
+    format!(
+        r#"
+
+Instead of Markdown, reply with JSON only, in this shape:
+{{"impactSummary": {{"text": "2-3 sentences.", "prUrls": ["https://..."]}}, "achievements": [{{"text": "One achievement.", "prUrls": ["https://..."]}}], "growth": [...], "collaboration": [...], "talkingPoints": [...]}}
+
+Every item of "growth", "collaboration" and "talkingPoints" has the same shape as an achievement. Cite each statement with the URLs of the pull requests it is based on, exactly as listed above. Leave "collaboration" empty unless the PRs show it. Write every "text" in {}."#,
+        language.name()
+    )
+}
+
+/// Reads the model's reply to a prompt ending in [`prompt_section`].
+///
+/// The impact summary and at least one achievement are required. Empty items
+/// are dropped, as are cited URLs that aren't among `prs`, so every citation
+/// points at real work.
+pub fn parse(reply: &str, prs: &[PullRequest]) -> Result<StructuredSummary, AppError> {
+    let mut summary: StructuredSummary =
+        serde_json::from_str(llm::json_body(reply)).map_err(|e| {
+            AppError::new(
+                ErrorCode::Parse,
+                format!("AI reply doesn't match the summary format: {}", e),
+            )
+        })?;
+
+    let clean = |point: &mut CitedPoint| {
+        point.text = point.text.trim().to_string();
+        let mut seen = Vec::new();
+        point.pr_urls.retain(|url| {
+            let keep = prs.iter().any(|pr| &pr.url == url) && !seen.contains(url);
+            seen.push(url.clone());
+            keep
+        });
+    };
+    clean(&mut summary.impact_summary);
+    for section in [
+        &mut summary.achievements,
+        &mut summary.growth,
+        &mut summary.collaboration,
+        &mut summary.talking_points,
+    ] {
+        section.iter_mut().for_each(clean);
+        section.retain(|point| !point.text.is_empty());
+    }
+
+    if summary.impact_summary.text.is_empty() || summary.achievements.is_empty() {
+        return Err(AppError::new(
+            ErrorCode::Parse,
+            "AI reply is missing the impact summary or achievements",
+        ));
+    }
+
+    Ok(summary)
+}
+
+/// The summary as Markdown with `language`'s headings, for copying, saving and
+/// exporting like a free-form one. Citations link to the PRs.
+pub fn to_markdown(summary: &StructuredSummary, language: Language, prs: &[PullRequest]) -> String {
+    let [impact, achievements, growth, collaboration, talking_points] = language.headings();
+    let mut sections = vec![format!(
+        "## {}\n\n{}{}",
+        impact,
+        summary.impact_summary.text,
+        citations(&summary.impact_summary, prs)
+    )];
+
+    for (heading, points) in [
+        (achievements, &summary.achievements),
+        (growth, &summary.growth),
+        (collaboration, &summary.collaboration),
+        (talking_points, &summary.talking_points),
+    ] {
+        if points.is_empty() {
+            continue;
+        }
+        let items: Vec<String> = points
+            .iter()
+            .map(|point| format!("- {}{}", point.text, citations(point, prs)))
+            .collect();
+        sections.push(format!("## {}\n\n{}", heading, items.join("\n")));
+    }
+
+    sections.join("\n\n")
+}
+
+/// ` ([acme/web#12](url), ...)`, or nothing when `point` cites no PRs.
+fn citations(point: &CitedPoint, prs: &[PullRequest]) -> String {
+    let links: Vec<String> = point
+        .pr_urls
+        .iter()
+        .filter_map(|url| prs.iter().find(|pr| &pr.url == url))
+        .map(|pr| {
+            let label = match pr.number {
+                Some(number) => format!("{}#{}", pr.repository.name_with_owner, number),
+                None => pr.title.clone(),
+            };
+            format!("[{}]({})", label, pr.url)
+        })
+        .collect();
+
+    if links.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", links.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Repository;
+
+    fn pr(number: i32) -> PullRequest {
+        PullRequest {
+            title: format!("PR {}", number),
+            url: format!("https://github.com/acme/web/pull/{}", number),
+            body: None,
+            closed_at: "2024-01-05".to_string(),
+            created_at: None,
+            number: Some(number),
+            repository: Repository {
+                name: "web".to_string(),
+                name_with_owner: "acme/web".to_string(),
+                host: None,
+            },
+            size: None,
+            labels: Vec::new(),
+            milestone: None,
+            closing_issues: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_parse_drops_unknown_citations_and_empty_items() {
+        let prs = vec![pr(1), pr(2)];
+
+        let summary = parse(
+            r#"Here it is:
+{"impactSummary": {"text": " Built retries. ", "prUrls": ["https://github.com/acme/web/pull/1", "https://github.com/acme/web/pull/9"]},
+ "achievements": [
+   {"text": "Added retries", "prUrls": ["https://github.com/acme/web/pull/1", "https://github.com/acme/web/pull/1"]},
+   {"text": "", "prUrls": []}
+ ],
+ "talkingPoints": [{"text": "The retry design", "prUrls": ["https://github.com/acme/web/pull/2"]}]}"#,
+            &prs,
+        )
+        .unwrap();
+
+        assert_eq!(summary.impact_summary.text, "Built retries.");
+        assert_eq!(summary.impact_summary.pr_urls, vec![prs[0].url.clone()]);
+        assert_eq!(summary.achievements.len(), 1);
+        assert_eq!(summary.achievements[0].pr_urls, vec![prs[0].url.clone()]);
+        assert!(summary.collaboration.is_empty());
+        assert_eq!(summary.talking_points[0].pr_urls, vec![prs[1].url.clone()]);
+    }
+
+    #[test]
+    fn test_parse_requires_impact_and_achievements() {
+        let prs = vec![pr(1)];
+
+        let missing = parse(r#"{"impactSummary": {"text": "Built retries."}}"#, &prs);
+        let empty = parse(
+            r#"{"impactSummary": {"text": "Built retries."}, "achievements": [{"text": " "}]}"#,
+            &prs,
+        );
+
+        assert_eq!(missing.unwrap_err().code, ErrorCode::Parse);
+        assert_eq!(empty.unwrap_err().code, ErrorCode::Parse);
+        assert_eq!(parse("Sorry", &prs).unwrap_err().code, ErrorCode::Parse);
+    }
+
+    #[test]
+    fn test_to_markdown_uses_language_headings() {
+        let prs = vec![pr(1)];
+        let summary = StructuredSummary {
+            impact_summary: CitedPoint {
+                text: "Construí novas tentativas.".to_string(),
+                pr_urls: vec![prs[0].url.clone()],
+            },
+            achievements: vec![CitedPoint {
+                text: "Adicionei tentativas".to_string(),
+                pr_urls: Vec::new(),
+            }],
+            growth: Vec::new(),
+            collaboration: Vec::new(),
+            talking_points: Vec::new(),
+        };
+
+        assert_eq!(
+            to_markdown(&summary, Language::PtBr, &prs),
+            "## Resumo de Impacto\n\nConstruí novas tentativas. ([acme/web#1](https://github.com/acme/web/pull/1))\n\n## Principais Conquistas\n\n- Adicionei tentativas"
+        );
+    }
+}
@@ -0,0 +1,327 @@
+use crate::error::{AppError, ErrorCode};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Template used when none is selected.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// The self-assessment prompt, used as `default` unless `default.md` overrides it.
+const BUILTIN_DEFAULT: &str = include_str!("templates/default.md");
+
+/// Placeholders a template may use, each written as `{{name}}`.
+pub const PLACEHOLDERS: [&str; 11] = [
+    "date_range",
+    "org_name",
+    "pr_count",
+    // "Here are the N merged pull requests:", or how chunk notes are grouped.
+    "pr_heading",
+    "pr_list",
+    "issue_count",
+    // Issues, with instructions for using them; empty when there are none.
+    "issue_section",
+    "review_count",
+    // Reviews given, with instructions for using them; empty when there are none.
+    "review_section",
+    // The career-ladder competencies; empty without a rubric.
+    "rubric_section",
+    // Asks for the summary in the requested language; empty for English.
+    "language_section",
+];
+
+/// A named prompt, stored as `<name>.md` in the templates directory.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PromptTemplate {
+    pub name: String,
+    pub content: String,
+    /// `true` for the built-in default, which has no file of its own.
+    #[serde(default)]
+    pub builtin: bool,
+}
+
+/// Names become file names, so only letters, digits, `-` and `_` are allowed.
+fn validate_name(name: &str) -> Result<(), AppError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(AppError::new(
+            ErrorCode::InvalidInput,
+            format!(
+                "Invalid template name {:?}: use letters, digits, '-' and '_'",
+                name
+            ),
+        ))
+    }
+}
+
+fn template_path(dir: &Path, name: &str) -> PathBuf {
+    dir.join(format!("{}.md", name))
+}
+
+fn io_error(action: &str, e: io::Error) -> AppError {
+    AppError::new(
+        ErrorCode::Io,
+        format!("Failed to {} prompt template: {}", action, e),
+    )
+}
+
+/// Lists the templates in `dir`, with the built-in default first unless a
+/// file overrides it.
+pub fn list(dir: &Path) -> Result<Vec<PromptTemplate>, AppError> {
+    let mut templates = Vec::new();
+
+    if dir.exists() {
+        let entries = fs::read_dir(dir).map_err(|e| io_error("list", e))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Some(name) = path
+                .file_stem()
+                .filter(|_| path.extension().is_some_and(|ext| ext == "md"))
+                .and_then(|stem| stem.to_str())
+                .filter(|name| validate_name(name).is_ok())
+            else {
+                continue;
+            };
+            templates.push(PromptTemplate {
+                name: name.to_string(),
+                content: fs::read_to_string(&path).map_err(|e| io_error("read", e))?,
+                builtin: false,
+            });
+        }
+    }
+    templates.sort_by(|a, b| a.name.cmp(&b.name));
+
+    if !templates.iter().any(|t| t.name == DEFAULT_TEMPLATE) {
+        templates.insert(
+            0,
+            PromptTemplate {
+                name: DEFAULT_TEMPLATE.to_string(),
+                content: BUILTIN_DEFAULT.to_string(),
+                builtin: true,
+            },
+        );
+    }
+
+    Ok(templates)
+}
+
+/// Reads the template called `name`, or the default one.
+pub fn load(dir: &Path, name: Option<&str>) -> Result<String, AppError> {
+    let name = name.filter(|n| !n.is_empty()).unwrap_or(DEFAULT_TEMPLATE);
+    validate_name(name)?;
+
+    let path = template_path(dir, name);
+    if path.exists() {
+        return fs::read_to_string(&path).map_err(|e| io_error("read", e));
+    }
+    if name == DEFAULT_TEMPLATE {
+        return Ok(BUILTIN_DEFAULT.to_string());
+    }
+
+    Err(AppError::new(
+        ErrorCode::InvalidInput,
+        format!("No prompt template named {:?}", name),
+    ))
+}
+
+/// Whether `template` is the built-in default, whose section headings are known.
+pub fn is_builtin_default(template: &str) -> bool {
+    template == BUILTIN_DEFAULT
+}
+
+/// Whether `template` contains `{{name}}`.
+pub fn uses_placeholder(template: &str, name: &str) -> bool {
+    template.split("{{").skip(1).any(|rest| {
+        rest.split_once("}}")
+            .is_some_and(|(placeholder, _)| placeholder.trim() == name)
+    })
+}
+
+/// Checks that `content` only uses known placeholders.
+pub fn validate(content: &str) -> Result<(), AppError> {
+    let placeholders: Vec<(&str, String)> =
+        PLACEHOLDERS.iter().map(|p| (*p, String::new())).collect();
+    render(content, &placeholders).map(|_| ())
+}
+
+/// Checks that `content` only uses known placeholders, then writes it to `dir`.
+pub fn save(dir: &Path, name: &str, content: &str) -> Result<(), AppError> {
+    validate_name(name)?;
+    validate(content)?;
+
+    fs::create_dir_all(dir).map_err(|e| io_error("save", e))?;
+    fs::write(template_path(dir, name), content).map_err(|e| io_error("save", e))
+}
+
+/// Deletes the template file called `name`. Deleting `default` restores the built-in one.
+pub fn delete(dir: &Path, name: &str) -> Result<(), AppError> {
+    validate_name(name)?;
+
+    match fs::remove_file(template_path(dir, name)) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(io_error("delete", e)),
+        _ => Ok(()),
+    }
+}
+
+/// Fills in the `{{placeholder}}`s of `template` in a single pass, so braces in
+/// the values (PR descriptions, say) are left alone.
+pub fn render(template: &str, values: &[(&str, String)]) -> Result<String, AppError> {
+    let mut output = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find("{{") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            output.push_str(&rest[start..]);
+            return Ok(output);
+        };
+
+        let name = after[..end].trim();
+        let value = values
+            .iter()
+            .find(|(key, _)| *key == name)
+            .map(|(_, value)| value)
+            .ok_or_else(|| {
+                AppError::new(
+                    ErrorCode::Config,
+                    format!(
+                        "Unknown placeholder {{{{{}}}}} in prompt template. Available: {}",
+                        name,
+                        PLACEHOLDERS.join(", ")
+                    ),
+                )
+            })?;
+        output.push_str(value);
+        rest = &after[end + 2..];
+    }
+
+    output.push_str(rest);
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("templates-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn test_render_replaces_placeholders_once() {
+        let rendered = render(
+            "PRs from {{ date_range }} at {{org_name}}:\n{{pr_list}}",
+            &[
+                ("date_range", "2024".to_string()),
+                ("org_name", "acme".to_string()),
+                ("pr_list", "- Fix {{org_name}} escaping".to_string()),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(
+            rendered,
+            "PRs from 2024 at acme:\n- Fix {{org_name}} escaping"
+        );
+    }
+
+    #[test]
+    fn test_render_rejects_unknown_placeholders() {
+        let error = render("{{team}}", &[]).unwrap_err();
+
+        assert_eq!(error.code, ErrorCode::Config);
+        assert!(error.message.starts_with("Unknown placeholder {{team}}"));
+    }
+
+    #[test]
+    fn test_builtin_default_uses_only_known_placeholders() {
+        let values: Vec<(&str, String)> = PLACEHOLDERS
+            .iter()
+            .map(|p| (*p, format!("<{}>", p)))
+            .collect();
+
+        let rendered = render(BUILTIN_DEFAULT, &values).unwrap();
+
+        assert!(rendered.contains("merged pull requests from <date_range> at <org_name>"));
+        assert!(
+            rendered.contains(
+                "<pr_heading>\n\n<pr_list><issue_section><review_section><rubric_section>"
+            )
+        );
+        assert!(rendered.ends_with("business metrics.<language_section>"));
+    }
+
+    #[test]
+    fn test_uses_placeholder() {
+        assert!(uses_placeholder(
+            "Summary.{{ language_section }}",
+            "language_section"
+        ));
+        assert!(!uses_placeholder(
+            "{{pr_list}} {{language",
+            "language_section"
+        ));
+        assert!(uses_placeholder(BUILTIN_DEFAULT, "language_section"));
+        assert!(is_builtin_default(BUILTIN_DEFAULT));
+    }
+
+    #[test]
+    fn test_files_override_and_extend_builtin() {
+        let dir = temp_dir("files");
+        save(
+            &dir,
+            "craft",
+            "Craft / Delivery / Influence for {{date_range}}",
+        )
+        .unwrap();
+
+        let listed = list(&dir).unwrap();
+        let loaded = load(&dir, Some("craft")).unwrap();
+        let default = load(&dir, None).unwrap();
+        save(&dir, DEFAULT_TEMPLATE, "Custom default").unwrap();
+        let overridden = load(&dir, None).unwrap();
+        delete(&dir, DEFAULT_TEMPLATE).unwrap();
+        let restored = list(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        let names: Vec<(&str, bool)> = listed
+            .iter()
+            .map(|t| (t.name.as_str(), t.builtin))
+            .collect();
+        assert_eq!(names, vec![("default", true), ("craft", false)]);
+        assert_eq!(loaded, "Craft / Delivery / Influence for {{date_range}}");
+        assert_eq!(default, BUILTIN_DEFAULT);
+        assert_eq!(overridden, "Custom default");
+        assert!(restored[0].builtin);
+    }
+
+    #[test]
+    fn test_rejects_bad_names_and_templates() {
+        let dir = temp_dir("invalid");
+
+        assert_eq!(
+            load(&dir, Some("../settings")).unwrap_err().code,
+            ErrorCode::InvalidInput
+        );
+        assert_eq!(
+            load(&dir, Some("missing")).unwrap_err().code,
+            ErrorCode::InvalidInput
+        );
+        assert_eq!(
+            save(&dir, "broken", "{{unknown}}").unwrap_err().code,
+            ErrorCode::Config
+        );
+        assert_eq!(
+            validate("{{ pr_list }} {{prs}}").unwrap_err().code,
+            ErrorCode::Config
+        );
+        assert!(validate(BUILTIN_DEFAULT).is_ok());
+        assert!(!dir.exists());
+    }
+}
@@ -19,8 +19,10 @@ import {
   MonthlyChart,
   ContributionHeatmap,
   RepoChart,
+  LabelChart,
   OrgChart,
   FloatingActions,
+  RubricEvidence,
 } from "@/components/dashboard";
 import { Button } from "@/components/ui/button";
 import { Textarea } from "@/components/ui/textarea";
@@ -45,6 +47,7 @@ import {
   Brain,
   Eye,
   Code,
+  Square,
 } from "lucide-react";
 import "./App.css";
 
@@ -65,19 +68,29 @@ function App() {
     aiSummary,
     orgData,
     repoData,
+    labelStats,
     monthlyData,
     loading,
     orgsLoading,
     aiLoading,
+    fetchProgress,
+    aiTokens,
+    aiChunkProgress,
+    aiMappingProgress,
+    aiRubricEvidence,
+    aiRubricError,
     error,
     aiError,
     hasSearched,
     savedReports,
     apiKey,
+    apiKeyRequired,
     setApiKey,
     saveSettings,
     generateReport,
+    cancelReport,
     generateAiSummary,
+    stopAiSummary,
     saveReport,
     loadReport,
     deleteReport,
@@ -194,11 +207,19 @@ function App() {
           orgName={orgName}
           organizations={organizations}
           isLoading={loading}
+          loadingLabel={
+            fetchProgress.authored?.itemsFetched
+              ? t("search.fetchingProgress", {
+                  count: fetchProgress.authored.itemsFetched,
+                })
+              : undefined
+          }
           isOrgsLoading={orgsLoading}
           onStartDateChange={setStartDate}
           onEndDateChange={setEndDate}
           onOrgNameChange={setOrgName}
           onSubmit={generateReport}
+          onCancel={cancelReport}
         />
 
         {hasSearched && pullRequests.length > 0 && (
@@ -268,6 +289,11 @@ function App() {
                     <MonthlyChart data={monthlyData} />
                     <OrgChart data={orgData} />
                     <RepoChart data={repoData} />
+                    {labelStats &&
+                      (labelStats.labels.length > 0 ||
+                        labelStats.milestones.length > 0) && (
+                        <LabelChart stats={labelStats} />
+                      )}
                   </div>
 
                   <ContributionHeatmap pullRequests={pullRequests} />
@@ -377,16 +403,29 @@ function App() {
                       <CardDescription>{t("ai.description")}</CardDescription>
                     </div>
                     <div className="ai-actions">
+                      {aiLoading && (
+                        <Button variant="outline" onClick={stopAiSummary}>
+                          <Square className="h-4 w-4" />
+                          {t("ai.stop")}
+                        </Button>
+                      )}
                       {!aiSummary && pullRequests.length > 0 && (
                         <Button
                           onClick={generateAiSummary}
-                          disabled={aiLoading || !apiKey}
+                          disabled={aiLoading || (apiKeyRequired && !apiKey)}
                           className="generate-ai-button"
                         >
                           {aiLoading ? (
                             <>
                               <Loader2 className="h-4 w-4 animate-spin" />
-                              {t("ai.generating")}
+                              {aiChunkProgress
+                                ? t("ai.summarizingChunks", {
+                                    done: aiChunkProgress.done + 1,
+                                    total: aiChunkProgress.total,
+                                  })
+                                : aiTokens > 0
+                                  ? t("ai.generatingTokens", { count: aiTokens })
+                                  : t("ai.generating")}
                             </>
                           ) : (
                             <>
@@ -400,7 +439,7 @@ function App() {
                   </div>
                 </CardHeader>
                 <CardContent>
-                  {!apiKey ? (
+                  {apiKeyRequired && !apiKey ? (
                     <div className="api-key-warning">
                       <AlertCircle className="h-5 w-5" />
                       <div>
@@ -476,6 +515,20 @@ function App() {
                           <ReactMarkdown>{aiSummary}</ReactMarkdown>
                         </div>
                       )}
+                      {aiLoading && aiMappingProgress ? (
+                        <p className="rubric-mapping text-sm text-muted-foreground">
+                          <Loader2 className="h-4 w-4 animate-spin inline" />{" "}
+                          {t("ai.mappingRubric", {
+                            done: aiMappingProgress.done + 1,
+                            total: aiMappingProgress.total,
+                          })}
+                        </p>
+                      ) : (
+                        <RubricEvidence
+                          evidence={aiRubricEvidence}
+                          error={aiRubricError}
+                        />
+                      )}
                     </div>
                   ) : pullRequests.length > 0 ? (
                     <div className="ai-placeholder">
@@ -0,0 +1,52 @@
+import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
+import { useApp } from "@/contexts/AppContext";
+import { CHART_COLORS } from "@/lib/helpers";
+import type { LabelCount, LabelStats } from "@/types";
+
+interface LabelChartProps {
+  stats: LabelStats;
+}
+
+export function LabelChart({ stats }: LabelChartProps) {
+  const { t } = useApp();
+
+  const renderGroup = (title: string, counts: LabelCount[]) =>
+    counts.length > 0 && (
+      <div className="label-group">
+        <div className="label-group-title">{title}</div>
+        <div className="repo-legend-list">
+          {counts.map((label, index) => (
+            <div
+              key={label.name}
+              className="repo-legend-item"
+              title={label.prUrls.join("\n")}
+            >
+              <span
+                className="repo-legend-color"
+                style={{
+                  background: CHART_COLORS[index % CHART_COLORS.length],
+                }}
+              />
+              <span className="repo-legend-name">{label.name}</span>
+              <span className="repo-legend-value">{label.count}</span>
+            </div>
+          ))}
+        </div>
+      </div>
+    );
+
+  return (
+    <Card className="chart-card label-chart">
+      <CardHeader>
+        <CardTitle className="text-base">{t("overview.prsByLabel")}</CardTitle>
+        <CardDescription>{t("overview.distributionLabels")}</CardDescription>
+      </CardHeader>
+      <CardContent>
+        <div className="label-chart-layout">
+          {renderGroup(t("overview.labels"), stats.labels)}
+          {renderGroup(t("overview.milestones"), stats.milestones)}
+        </div>
+      </CardContent>
+    </Card>
+  );
+}
@@ -0,0 +1,59 @@
+import { AlertCircle, Target } from "lucide-react";
+import { Badge } from "@/components/ui/badge";
+import { useApp } from "@/contexts/AppContext";
+import type { CompetencyEvidence } from "@/types";
+
+interface RubricEvidenceProps {
+  evidence: CompetencyEvidence[] | null;
+  error: string | null;
+}
+
+export function RubricEvidence({ evidence, error }: RubricEvidenceProps) {
+  const { t } = useApp();
+
+  if (!evidence && !error) return null;
+
+  return (
+    <div className="rubric-evidence">
+      <h3 className="rubric-title">
+        <Target className="h-4 w-4" />
+        {t("ai.rubricEvidence")}
+      </h3>
+      {error ? (
+        <p className="text-sm text-muted-foreground">
+          <AlertCircle className="h-4 w-4 inline text-destructive" /> {error}
+        </p>
+      ) : (
+        evidence?.map((competency) => (
+          <div key={competency.competencyId} className="rubric-competency">
+            <div className="rubric-competency-header">
+              <span className="font-medium">{competency.competency}</span>
+              <Badge variant="secondary">{competency.evidence.length}</Badge>
+            </div>
+            {competency.evidence.length > 0 ? (
+              <ul className="rubric-evidence-list">
+                {competency.evidence.map((item) => (
+                  <li key={item.url}>
+                    <a href={item.url} target="_blank" rel="noopener noreferrer">
+                      {item.title}
+                    </a>{" "}
+                    <span className="text-muted-foreground">
+                      ({item.nameWithOwner})
+                    </span>
+                    {item.note && (
+                      <p className="text-sm text-muted-foreground">{item.note}</p>
+                    )}
+                  </li>
+                ))}
+              </ul>
+            ) : (
+              <p className="text-sm text-muted-foreground">
+                {t("ai.noRubricEvidence")}
+              </p>
+            )}
+          </div>
+        ))
+      )}
+    </div>
+  );
+}
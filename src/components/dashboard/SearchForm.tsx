@@ -1,4 +1,4 @@
-import { Calendar, Building2, Loader2, GitPullRequest } from "lucide-react";
+import { Calendar, Building2, Loader2, GitPullRequest, X } from "lucide-react";
 import { Card, CardContent, CardDescription, CardHeader, CardTitle } from "@/components/ui/card";
 import { Button } from "@/components/ui/button";
 import { DatePicker } from "@/components/ui/date-picker";
@@ -17,11 +17,14 @@ interface SearchFormProps {
   orgName: string;
   organizations: string[];
   isLoading: boolean;
+  /** Replaces the default "Fetching..." text while loading. */
+  loadingLabel?: string;
   isOrgsLoading: boolean;
   onStartDateChange: (date: string) => void;
   onEndDateChange: (date: string) => void;
   onOrgNameChange: (org: string) => void;
   onSubmit: () => void;
+  onCancel?: () => void;
 }
 
 export function SearchForm({
@@ -30,11 +33,13 @@ export function SearchForm({
   orgName,
   organizations,
   isLoading,
+  loadingLabel,
   isOrgsLoading,
   onStartDateChange,
   onEndDateChange,
   onOrgNameChange,
   onSubmit,
+  onCancel,
 }: SearchFormProps) {
   const { t } = useApp();
 
@@ -105,7 +110,7 @@ export function SearchForm({
               {isLoading ? (
                 <>
                   <Loader2 className="h-4 w-4 animate-spin" />
-                  {t("search.fetching")}
+                  {loadingLabel ?? t("search.fetching")}
                 </>
               ) : (
                 <>
@@ -114,6 +119,12 @@ export function SearchForm({
                 </>
               )}
             </Button>
+            {isLoading && onCancel && (
+              <Button onClick={onCancel} variant="outline" size="lg">
+                <X className="h-4 w-4" />
+                {t("search.cancel")}
+              </Button>
+            )}
           </div>
         </div>
       </CardContent>
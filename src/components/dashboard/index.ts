@@ -4,5 +4,7 @@ export { ExtendedStats } from "./ExtendedStats";
 export { MonthlyChart } from "./MonthlyChart";
 export { ContributionHeatmap } from "./ContributionHeatmap";
 export { RepoChart } from "./RepoChart";
+export { LabelChart } from "./LabelChart";
 export { OrgChart } from "./OrgChart";
 export { FloatingActions } from "./FloatingActions";
+export { RubricEvidence } from "./RubricEvidence";
@@ -7,9 +7,9 @@ import {
 } from "react";
 import enUS from "../locales/en-US.json";
 import ptBR from "../locales/pt-BR.json";
+import type { Locale } from "@/types";
 
 type Theme = "system" | "light" | "dark";
-type Locale = "en-US" | "pt-BR";
 
 interface Translations {
   [key: string]: string | Translations;
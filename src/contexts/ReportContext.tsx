@@ -7,16 +7,21 @@ import {
   type ReactNode,
 } from "react";
 import type {
+  CompetencyEvidence,
+  FetchProgress,
+  StructuredSummary,
   PullRequest,
   ReviewedPullRequest,
   SavedReport,
   ChartDataPoint,
+  LabelStats,
   MonthlyDataPoint,
 } from "@/types";
 import { useGitHubData } from "@/hooks/useGitHubData";
 import { useAiSummary } from "@/hooks/useAiSummary";
 import { useReports } from "@/hooks/useReports";
 import { useSettings } from "@/hooks/useSettings";
+import { useApp } from "@/contexts/AppContext";
 import {
   formatDate,
   getDefaultDateRange,
@@ -33,6 +38,7 @@ interface ReportContextType {
   setOrgName: (org: string) => void;
   pullRequests: PullRequest[];
   reviewedPrs: ReviewedPullRequest[];
+  labelStats: LabelStats | null;
   organizations: string[];
   aiSummary: string | null;
   orgData: ChartDataPoint[];
@@ -41,6 +47,13 @@ interface ReportContextType {
   loading: boolean;
   orgsLoading: boolean;
   aiLoading: boolean;
+  fetchProgress: Partial<Record<FetchProgress["kind"], FetchProgress>>;
+  aiTokens: number;
+  aiChunkProgress: { done: number; total: number } | null;
+  aiMappingProgress: { done: number; total: number } | null;
+  aiRubricEvidence: CompetencyEvidence[] | null;
+  aiRubricError: string | null;
+  aiStructuredSummary: StructuredSummary | null;
   error: string | null;
   aiError: string | null;
   hasSearched: boolean;
@@ -48,10 +61,14 @@ interface ReportContextType {
   loadedReportId: string | null;
   loadedReportHadSummary: boolean;
   apiKey: string;
+  /** Whether the configured AI provider needs `apiKey`. */
+  apiKeyRequired: boolean;
   setApiKey: (key: string) => void;
   saveSettings: () => Promise<void>;
   generateReport: () => void;
+  cancelReport: () => Promise<void>;
   generateAiSummary: () => Promise<void>;
+  stopAiSummary: () => Promise<void>;
   saveReport: (name: string) => Promise<void>;
   updateReport: () => Promise<void>;
   loadReport: (report: SavedReport) => void;
@@ -87,17 +104,21 @@ export function ReportProvider({ children }: ReportProviderProps) {
   const {
     pullRequests,
     reviewedPrs,
+    labelStats,
     organizations,
     loading,
     orgsLoading,
     error,
     hasSearched,
+    progress: fetchProgress,
     fetchReport,
     fetchOrganizations,
+    cancelFetch,
     setPullRequests,
   } = useGitHubData();
 
-  const { apiKey, setApiKey, saveSettings } = useSettings();
+  const { apiKey, apiKeyRequired, setApiKey, saveSettings } = useSettings();
+  const { locale } = useApp();
 
   const {
     savedReports,
@@ -110,8 +131,15 @@ export function ReportProvider({ children }: ReportProviderProps) {
   const {
     summary: aiSummary,
     isLoading: aiLoading,
+    tokens: aiTokens,
+    chunkProgress: aiChunkProgress,
+    mappingProgress: aiMappingProgress,
+    rubricEvidence: aiRubricEvidence,
+    rubricError: aiRubricError,
+    structured: aiStructuredSummary,
     error: aiError,
     generateSummary,
+    stopSummary: stopAiSummary,
     setSummary: setAiSummary,
   } = useAiSummary();
 
@@ -165,12 +193,16 @@ export function ReportProvider({ children }: ReportProviderProps) {
   const generateAiSummary = useCallback(async () => {
     await generateSummary({
       apiKey,
+      apiKeyRequired,
       pullRequests,
       dateRange: getFormattedDateRange(),
       orgName: getFormattedOrgName(),
+      language: locale,
     });
   }, [
     apiKey,
+    apiKeyRequired,
+    locale,
     pullRequests,
     getFormattedDateRange,
     getFormattedOrgName,
@@ -245,6 +277,7 @@ export function ReportProvider({ children }: ReportProviderProps) {
       setOrgName,
       pullRequests,
       reviewedPrs,
+      labelStats,
       organizations,
       aiSummary,
       orgData,
@@ -253,6 +286,13 @@ export function ReportProvider({ children }: ReportProviderProps) {
       loading,
       orgsLoading,
       aiLoading,
+      fetchProgress,
+      aiTokens,
+      aiChunkProgress,
+      aiMappingProgress,
+      aiRubricEvidence,
+      aiRubricError,
+      aiStructuredSummary,
       error,
       aiError,
       hasSearched,
@@ -260,10 +300,13 @@ export function ReportProvider({ children }: ReportProviderProps) {
       loadedReportId,
       loadedReportHadSummary,
       apiKey,
+      apiKeyRequired,
       setApiKey,
       saveSettings,
       generateReport,
+      cancelReport: cancelFetch,
       generateAiSummary,
+      stopAiSummary,
       saveReport,
       updateReport,
       loadReport,
@@ -280,6 +323,7 @@ export function ReportProvider({ children }: ReportProviderProps) {
       handleEndDateChange,
       pullRequests,
       reviewedPrs,
+      labelStats,
       organizations,
       aiSummary,
       orgData,
@@ -288,6 +332,13 @@ export function ReportProvider({ children }: ReportProviderProps) {
       loading,
       orgsLoading,
       aiLoading,
+      fetchProgress,
+      aiTokens,
+      aiChunkProgress,
+      aiMappingProgress,
+      aiRubricEvidence,
+      aiRubricError,
+      aiStructuredSummary,
       error,
       aiError,
       hasSearched,
@@ -295,10 +346,13 @@ export function ReportProvider({ children }: ReportProviderProps) {
       loadedReportId,
       loadedReportHadSummary,
       apiKey,
+      apiKeyRequired,
       setApiKey,
       saveSettings,
       generateReport,
+      cancelFetch,
       generateAiSummary,
+      stopAiSummary,
       saveReport,
       updateReport,
       loadReport,
@@ -2,6 +2,7 @@ import { describe, it, expect, vi, beforeEach } from "vitest";
 import { renderHook, act, waitFor } from "@testing-library/react";
 import { useAiSummary } from "./useAiSummary";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { mockPullRequest } from "@/test/mocks/tauri";
 
 vi.mock("@tauri-apps/api/core");
@@ -75,16 +76,93 @@ describe("useAiSummary", () => {
     });
 
     expect(result.current.error).toBe(
-      "Please configure your OpenAI API key in settings"
+      "Please configure your AI API key in settings"
     );
     expect(result.current.summary).toBeNull();
   });
 
+  it("generates without an API key for local providers", async () => {
+    vi.mocked(invoke).mockResolvedValueOnce({
+      success: true,
+      summary: "Local summary",
+      error: null,
+    });
+
+    const { result } = renderHook(() => useAiSummary());
+
+    await act(async () => {
+      await result.current.generateSummary({
+        apiKey: "",
+        apiKeyRequired: false,
+        pullRequests: [mockPullRequest],
+        dateRange: "Nov 1 - Nov 27",
+        orgName: "org",
+      });
+    });
+
+    expect(result.current.error).toBeNull();
+    expect(result.current.summary).toBe("Local summary");
+  });
+
+  it("streams chunks into the summary and can be stopped", async () => {
+    const handlers: Record<string, (event: { payload: unknown }) => void> = {};
+    vi.mocked(listen).mockImplementation(async (event, handler) => {
+      handlers[event as string] = handler as (event: { payload: unknown }) => void;
+      return () => {};
+    });
+    let finish: (value: unknown) => void = () => {};
+    vi.mocked(invoke).mockImplementation(async (cmd: string, args?: unknown) => {
+      if (cmd === "cancel_fetch") {
+        finish({
+          success: true,
+          summary: "Built the",
+          stopped: true,
+          error: null,
+        });
+        return true;
+      }
+      const { requestId } = args as { requestId: string };
+      handlers["ai-chunk"]?.({ payload: { requestId, delta: "Built " } });
+      handlers["ai-chunk"]?.({ payload: { requestId, delta: "the" } });
+      return new Promise((resolve) => {
+        finish = resolve;
+      });
+    });
+
+    const { result } = renderHook(() => useAiSummary());
+
+    act(() => {
+      result.current.generateSummary({
+        apiKey: "sk-test",
+        pullRequests: [mockPullRequest],
+        dateRange: "Nov 1 - Nov 27",
+        orgName: "org",
+      });
+    });
+
+    await waitFor(() => {
+      expect(result.current.summary).toBe("Built the");
+    });
+    expect(result.current.isLoading).toBe(true);
+
+    await act(async () => {
+      await result.current.stopSummary();
+    });
+
+    await waitFor(() => {
+      expect(result.current.isLoading).toBe(false);
+    });
+    expect(invoke).toHaveBeenCalledWith("cancel_fetch", {
+      requestId: expect.any(String),
+    });
+    expect(result.current.summary).toBe("Built the");
+  });
+
   it("handles API error", async () => {
     vi.mocked(invoke).mockResolvedValue({
       success: false,
       summary: null,
-      error: "API rate limit exceeded",
+      error: { code: "rate_limited", message: "API rate limit exceeded" },
     });
 
     const { result } = renderHook(() => useAiSummary());
@@ -142,6 +220,161 @@ describe("useAiSummary", () => {
     expect(result.current.summary).toBeNull();
   });
 
+  it("reports chunk progress and passes the chunking strategy", async () => {
+    const handlers: Record<string, (event: { payload: unknown }) => void> = {};
+    vi.mocked(listen).mockImplementation(async (event, handler) => {
+      handlers[event as string] = handler as (event: { payload: unknown }) => void;
+      return () => {};
+    });
+    let finish: (value: unknown) => void = () => {};
+    vi.mocked(invoke).mockImplementation(async (_cmd: string, args?: unknown) => {
+      const { requestId } = args as { requestId: string };
+      handlers["ai-progress"]?.({
+        payload: {
+          requestId,
+          phase: "summarizing",
+          tokens: 120,
+          chunksDone: 1,
+          chunks: 3,
+        },
+      });
+      return new Promise((resolve) => {
+        finish = resolve;
+      });
+    });
+
+    const { result } = renderHook(() => useAiSummary());
+
+    act(() => {
+      result.current.generateSummary({
+        apiKey: "sk-test",
+        pullRequests: [mockPullRequest],
+        dateRange: "Nov 1 - Nov 27",
+        orgName: "org",
+        chunking: "month",
+      });
+    });
+
+    await waitFor(() => {
+      expect(result.current.chunkProgress).toEqual({ done: 1, total: 3 });
+    });
+    expect(invoke).toHaveBeenCalledWith(
+      "generate_ai_summary",
+      expect.objectContaining({ chunking: "month" })
+    );
+
+    await act(async () => {
+      finish({ success: true, summary: "Summary", error: null });
+    });
+
+    await waitFor(() => {
+      expect(result.current.isLoading).toBe(false);
+    });
+    expect(result.current.chunkProgress).toBeNull();
+  });
+
+  it("passes the summary language", async () => {
+    const { result } = renderHook(() => useAiSummary());
+
+    await act(async () => {
+      await result.current.generateSummary({
+        apiKey: "sk-test",
+        pullRequests: [mockPullRequest],
+        dateRange: "1 nov - 27 nov",
+        orgName: "org",
+        language: "pt-BR",
+      });
+    });
+
+    expect(invoke).toHaveBeenCalledWith(
+      "generate_ai_summary",
+      expect.objectContaining({ language: "pt-BR" })
+    );
+  });
+
+  it("returns structured sections in JSON mode", async () => {
+    const structured = {
+      impactSummary: { text: "Built retries.", prUrls: [mockPullRequest.url] },
+      achievements: [{ text: "Added retries", prUrls: [mockPullRequest.url] }],
+      growth: [],
+      collaboration: [],
+      talkingPoints: [],
+    };
+    vi.mocked(invoke).mockResolvedValue({
+      success: true,
+      summary: "## Impact Summary\n\nBuilt retries.",
+      structured,
+      error: null,
+    });
+
+    const { result } = renderHook(() => useAiSummary());
+
+    await act(async () => {
+      await result.current.generateSummary({
+        apiKey: "sk-test",
+        pullRequests: [mockPullRequest],
+        dateRange: "Nov 1 - Nov 27",
+        orgName: "org",
+        format: "json",
+      });
+    });
+
+    expect(invoke).toHaveBeenCalledWith(
+      "generate_ai_summary",
+      expect.objectContaining({ format: "json" })
+    );
+    expect(result.current.structured).toEqual(structured);
+    expect(result.current.summary).toBe("## Impact Summary\n\nBuilt retries.");
+  });
+
+  it("keeps the summary when rubric mapping fails", async () => {
+    const evidence = [
+      {
+        competencyId: "craft",
+        competency: "Craft",
+        evidence: [
+          {
+            title: mockPullRequest.title,
+            url: mockPullRequest.url,
+            nameWithOwner: mockPullRequest.repository.nameWithOwner,
+            note: "Added retries.",
+          },
+        ],
+      },
+    ];
+    vi.mocked(invoke).mockResolvedValue({
+      success: true,
+      summary: "Summary",
+      rubric: evidence,
+      error: { code: "parse", message: "Failed to parse rubric mapping" },
+    });
+
+    const { result } = renderHook(() => useAiSummary());
+
+    await act(async () => {
+      await result.current.generateSummary({
+        apiKey: "sk-test",
+        pullRequests: [mockPullRequest],
+        dateRange: "Nov 1 - Nov 27",
+        orgName: "org",
+        rubricPath: "/home/me/ladder.yaml",
+        level: "L5",
+      });
+    });
+
+    expect(invoke).toHaveBeenCalledWith(
+      "generate_ai_summary",
+      expect.objectContaining({
+        rubricPath: "/home/me/ladder.yaml",
+        level: "L5",
+      })
+    );
+    expect(result.current.summary).toBe("Summary");
+    expect(result.current.rubricEvidence).toEqual(evidence);
+    expect(result.current.rubricError).toBe("Failed to parse rubric mapping");
+    expect(result.current.error).toBeNull();
+  });
+
   it("clears error", () => {
     const { result } = renderHook(() => useAiSummary());
 
@@ -1,36 +1,119 @@
-import { useState, useCallback } from "react";
-import type { PullRequest } from "@/types";
+import { useState, useCallback, useRef } from "react";
+import type {
+  ChunkStrategy,
+  CompetencyEvidence,
+  Locale,
+  PullRequest,
+  StructuredSummary,
+  SummaryFormat,
+} from "@/types";
 import * as tauriService from "@/services/tauri";
+import { generateId } from "@/lib/helpers";
 
 interface UseAiSummaryReturn {
   summary: string | null;
   isLoading: boolean;
+  /** Completion tokens generated so far for the summary in progress. */
+  tokens: number;
+  /** Notes written so far when a large PR set is summarized in chunks. */
+  chunkProgress: ChunkProgress | null;
+  /** Batches of PRs matched so far to the rubric's competencies. */
+  mappingProgress: ChunkProgress | null;
+  /** PRs matched to each rubric competency, when a rubric is configured. */
+  rubricEvidence: CompetencyEvidence[] | null;
+  /** Why matching PRs to the rubric failed; the summary itself still succeeded. */
+  rubricError: string | null;
+  /** The summary's sections, when it was requested as JSON. */
+  structured: StructuredSummary | null;
   error: string | null;
   generateSummary: (params: GenerateSummaryParams) => Promise<void>;
+  stopSummary: () => Promise<void>;
   setSummary: (summary: string | null) => void;
   clearError: () => void;
 }
 
+interface ChunkProgress {
+  done: number;
+  total: number;
+}
+
 interface GenerateSummaryParams {
   apiKey: string;
+  /** `false` for local providers, which run without a key. Defaults to `true`. */
+  apiKeyRequired?: boolean;
   pullRequests: PullRequest[];
   dateRange: string;
   orgName: string;
+  chunking?: ChunkStrategy;
+  /** Name of the prompt template to use; the default one when unset. */
+  template?: string;
+  /** Career-ladder rubric to map PRs to; the one in settings when unset. */
+  rubricPath?: string;
+  /** Level of the rubric to assess against; the one in settings when unset. */
+  level?: string;
+  /** Language to write the summary in. Defaults to English. */
+  language?: Locale;
+  /** `"json"` for typed sections instead of streamed Markdown. Defaults to `"markdown"`. */
+  format?: SummaryFormat;
 }
 
 export function useAiSummary(): UseAiSummaryReturn {
   const [summary, setSummary] = useState<string | null>(null);
   const [isLoading, setIsLoading] = useState(false);
+  const [tokens, setTokens] = useState(0);
+  const [chunkProgress, setChunkProgress] = useState<ChunkProgress | null>(
+    null
+  );
+  const [mappingProgress, setMappingProgress] = useState<ChunkProgress | null>(
+    null
+  );
+  const [rubricEvidence, setRubricEvidence] = useState<
+    CompetencyEvidence[] | null
+  >(null);
+  const [rubricError, setRubricError] = useState<string | null>(null);
+  const [structured, setStructured] = useState<StructuredSummary | null>(
+    null
+  );
   const [error, setError] = useState<string | null>(null);
+  const requestIdRef = useRef<string | null>(null);
 
   const generateSummary = useCallback(async (params: GenerateSummaryParams) => {
-    if (!params.apiKey) {
-      setError("Please configure your OpenAI API key in settings");
+    if (params.apiKeyRequired !== false && !params.apiKey) {
+      setError("Please configure your AI API key in settings");
       return;
     }
 
+    const requestId = generateId();
+    requestIdRef.current = requestId;
     setIsLoading(true);
     setError(null);
+    setTokens(0);
+    setChunkProgress(null);
+    setMappingProgress(null);
+    setRubricEvidence(null);
+    setRubricError(null);
+    setStructured(null);
+    setSummary(null);
+    const unlistenProgress = await tauriService.onAiProgress((update) => {
+      if (update.requestId === requestId) {
+        setTokens(update.tokens);
+        setChunkProgress(
+          update.phase === "summarizing"
+            ? { done: update.chunksDone, total: update.chunks }
+            : null
+        );
+        setMappingProgress(
+          update.phase === "mapping"
+            ? { done: update.chunksDone, total: update.chunks }
+            : null
+        );
+      }
+    });
+    const unlistenChunks = await tauriService.onAiChunk((chunk) => {
+      if (chunk.requestId === requestId) {
+        setSummary((current) => (current ?? "") + chunk.delta);
+      }
+    });
     await new Promise((resolve) => setTimeout(resolve, 50));
 
     try {
@@ -38,21 +121,44 @@ export function useAiSummary(): UseAiSummaryReturn {
         params.apiKey,
         params.pullRequests,
         params.dateRange,
-        params.orgName
+        params.orgName,
+        null,
+        null,
+        params.chunking ?? null,
+        params.template ?? null,
+        params.rubricPath ?? null,
+        params.level ?? null,
+        params.language ?? null,
+        params.format ?? null,
+        requestId
       );
 
       if (result.success && result.summary) {
         setSummary(result.summary);
+        setRubricEvidence(result.rubric ?? null);
+        setRubricError(result.error?.message ?? null);
+        setStructured(result.structured ?? null);
       } else {
-        setError(result.error || "Failed to generate summary");
+        setError(result.error?.message || "Failed to generate summary");
       }
     } catch (err) {
       setError(err instanceof Error ? err.message : "Failed to generate summary");
     } finally {
+      unlistenProgress();
+      unlistenChunks();
+      requestIdRef.current = null;
+      setChunkProgress(null);
+      setMappingProgress(null);
       setIsLoading(false);
     }
   }, []);
 
+  const stopSummary = useCallback(async () => {
+    if (requestIdRef.current) {
+      await tauriService.cancelFetch(requestIdRef.current);
+    }
+  }, []);
+
   const clearError = useCallback(() => {
     setError(null);
   }, []);
@@ -60,8 +166,15 @@ export function useAiSummary(): UseAiSummaryReturn {
   return {
     summary,
     isLoading,
+    tokens,
+    chunkProgress,
+    mappingProgress,
+    rubricEvidence,
+    rubricError,
+    structured,
     error,
     generateSummary,
+    stopSummary,
     setSummary,
     clearError,
   };
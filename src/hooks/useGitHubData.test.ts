@@ -2,6 +2,7 @@ import { describe, it, expect, vi, beforeEach } from "vitest";
 import { renderHook, act, waitFor } from "@testing-library/react";
 import { useGitHubData } from "./useGitHubData";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { mockPullRequest, mockReviewedPr } from "@/test/mocks/tauri";
 
 vi.mock("@tauri-apps/api/core");
@@ -81,6 +82,7 @@ describe("useGitHubData", () => {
       startDate: "2024-01-01",
       endDate: "2024-06-01",
       orgName: "my-org",
+      requestId: expect.any(String),
     });
   });
 
@@ -95,6 +97,7 @@ describe("useGitHubData", () => {
       startDate: "2024-01-01",
       endDate: "2024-06-01",
       orgName: null,
+      requestId: expect.any(String),
     });
   });
 
@@ -104,7 +107,7 @@ describe("useGitHubData", () => {
         return {
           success: false,
           data: null,
-          error: "GitHub CLI not found",
+          error: { code: "gh_not_installed", message: "GitHub CLI not found" },
         };
       }
       if (cmd === "fetch_reviewed_prs") {
@@ -134,6 +137,92 @@ describe("useGitHubData", () => {
     expect(result.current.error).toBe("Network error");
   });
 
+  it("cancels an in-flight fetch", async () => {
+    vi.mocked(invoke).mockImplementation(async (cmd: string) => {
+      if (cmd === "cancel_fetch") {
+        return true;
+      }
+      return new Promise((resolve) =>
+        setTimeout(
+          () =>
+            resolve({
+              success: false,
+              data: null,
+              error: { code: "cancelled", message: "The request was cancelled" },
+            }),
+          50
+        )
+      );
+    });
+
+    const { result } = renderHook(() => useGitHubData());
+
+    act(() => {
+      result.current.fetchReport("2024-01-01", "2024-06-01", null);
+    });
+    await waitFor(() => {
+      expect(result.current.loading).toBe(true);
+    });
+
+    await act(async () => {
+      await result.current.cancelFetch();
+    });
+
+    expect(invoke).toHaveBeenCalledWith("cancel_fetch", {
+      requestId: expect.any(String),
+    });
+    expect(result.current.loading).toBe(false);
+    await waitFor(() => {
+      expect(invoke).toHaveBeenCalledWith("fetch_reviewed_prs", {
+        startDate: "2024-01-01",
+        endDate: "2024-06-01",
+        requestId: expect.any(String),
+      });
+    });
+    expect(result.current.error).toBeNull();
+  });
+
+  it("tracks fetch progress for the current request", async () => {
+    let emit: ((event: { payload: unknown }) => void) | undefined;
+    vi.mocked(listen).mockImplementation(async (_event, handler) => {
+      emit = handler as typeof emit;
+      return () => {};
+    });
+    vi.mocked(invoke).mockImplementation(async (cmd: string, args?: unknown) => {
+      const { requestId } = args as { requestId: string };
+      if (cmd === "fetch_github_activity") {
+        emit?.({
+          payload: {
+            requestId,
+            kind: "authored",
+            phase: "searching",
+            pagesFetched: 1,
+            itemsFetched: 42,
+          },
+        });
+        emit?.({
+          payload: {
+            requestId: "another-request",
+            kind: "authored",
+            phase: "searching",
+            pagesFetched: 9,
+            itemsFetched: 900,
+          },
+        });
+      }
+      return { success: true, data: [], error: null };
+    });
+
+    const { result } = renderHook(() => useGitHubData());
+
+    await act(async () => {
+      await result.current.fetchReport("2024-01-01", "2024-06-01", null);
+    });
+
+    expect(listen).toHaveBeenCalledWith("fetch-progress", expect.any(Function));
+    expect(result.current.progress.authored?.itemsFetched).toBe(42);
+  });
+
   it("fetches organizations", async () => {
     const { result } = renderHook(() => useGitHubData());
 
@@ -149,7 +238,7 @@ describe("useGitHubData", () => {
   it("handles organizations fetch error", async () => {
     vi.mocked(invoke).mockImplementation(async (cmd: string) => {
       if (cmd === "fetch_organizations") {
-        return { success: false, organizations: null, error: "Error" };
+        return { success: false, organizations: null, error: { code: "api", message: "Error" } };
       }
     });
     const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
@@ -191,5 +280,31 @@ describe("useGitHubData", () => {
 
     expect(result.current.pullRequests).toHaveLength(2);
   });
-});
 
+  it("keeps label stats from the fetch until pull requests are replaced", async () => {
+    const labelStats = {
+      labels: [{ name: "bug", count: 1, prUrls: [mockPullRequest.url] }],
+      milestones: [],
+    };
+    vi.mocked(invoke).mockImplementation(async (cmd: string) => {
+      if (cmd === "fetch_github_activity") {
+        return { success: true, data: [mockPullRequest], labelStats, error: null };
+      }
+      return { success: true, data: [], error: null };
+    });
+
+    const { result } = renderHook(() => useGitHubData());
+
+    await act(async () => {
+      await result.current.fetchReport("2024-01-01", "2024-06-01", null);
+    });
+
+    expect(result.current.labelStats).toEqual(labelStats);
+
+    act(() => {
+      result.current.setPullRequests([mockPullRequest]);
+    });
+
+    expect(result.current.labelStats).toBeNull();
+  });
+});
@@ -1,17 +1,27 @@
-import { useState, useCallback } from "react";
-import type { PullRequest, ReviewedPullRequest } from "@/types";
+import { useState, useCallback, useRef } from "react";
+import type {
+  FetchProgress,
+  LabelStats,
+  PullRequest,
+  ReviewedPullRequest,
+} from "@/types";
 import * as tauriService from "@/services/tauri";
+import { generateId } from "@/lib/helpers";
 
 interface UseGitHubDataReturn {
   pullRequests: PullRequest[];
   reviewedPrs: ReviewedPullRequest[];
+  /** Label and milestone counts from the last fetch; `null` for pull requests set directly. */
+  labelStats: LabelStats | null;
   organizations: string[];
   loading: boolean;
   orgsLoading: boolean;
   error: string | null;
   hasSearched: boolean;
+  progress: Partial<Record<FetchProgress["kind"], FetchProgress>>;
   fetchReport: (startDate: string, endDate: string, orgName: string | null) => Promise<void>;
   fetchOrganizations: (startDate: string, endDate: string) => Promise<void>;
+  cancelFetch: () => Promise<void>;
   clearError: () => void;
   setPullRequests: (prs: PullRequest[]) => void;
 }
@@ -19,20 +29,37 @@ interface UseGitHubDataReturn {
 export function useGitHubData(): UseGitHubDataReturn {
   const [pullRequests, setPullRequests] = useState<PullRequest[]>([]);
   const [reviewedPrs, setReviewedPrs] = useState<ReviewedPullRequest[]>([]);
+  const [labelStats, setLabelStats] = useState<LabelStats | null>(null);
   const [organizations, setOrganizations] = useState<string[]>([]);
   const [loading, setLoading] = useState(false);
   const [orgsLoading, setOrgsLoading] = useState(false);
   const [error, setError] = useState<string | null>(null);
   const [hasSearched, setHasSearched] = useState(false);
+  const [progress, setProgress] = useState<
+    Partial<Record<FetchProgress["kind"], FetchProgress>>
+  >({});
+  const requestIdRef = useRef<string | null>(null);
 
   const fetchReport = useCallback(async (
     startDate: string,
     endDate: string,
     orgName: string | null
   ) => {
+    if (requestIdRef.current) {
+      tauriService.cancelFetch(requestIdRef.current);
+    }
+    const requestId = generateId();
+    requestIdRef.current = requestId;
+
     setLoading(true);
     setError(null);
     setHasSearched(true);
+    setProgress({});
+    const unlisten = await tauriService.onFetchProgress((update) => {
+      if (update.requestId === requestId) {
+        setProgress((current) => ({ ...current, [update.kind]: update }));
+      }
+    });
     await new Promise((resolve) => setTimeout(resolve, 50));
 
     try {
@@ -40,16 +67,27 @@ export function useGitHubData(): UseGitHubDataReturn {
         tauriService.fetchGitHubActivity(
           startDate,
           endDate,
-          orgName === "__all__" ? null : orgName
+          orgName === "__all__" ? null : orgName,
+          requestId
         ),
-        tauriService.fetchReviewedPrs(startDate, endDate),
+        tauriService.fetchReviewedPrs(startDate, endDate, requestId),
       ]);
 
+      // A newer fetch has started, or this one was cancelled.
+      if (
+        requestIdRef.current !== requestId ||
+        authResult.error?.code === "cancelled"
+      ) {
+        return;
+      }
+
       if (authResult.success && authResult.data) {
         setPullRequests(authResult.data);
+        setLabelStats(authResult.labelStats ?? null);
       } else {
-        setError(authResult.error || "An unknown error occurred");
+        setError(authResult.error?.message || "An unknown error occurred");
         setPullRequests([]);
+        setLabelStats(null);
       }
 
       if (reviewResult.success && reviewResult.data) {
@@ -60,12 +98,28 @@ export function useGitHubData(): UseGitHubDataReturn {
     } catch (err) {
       setError(err instanceof Error ? err.message : "Failed to fetch data");
       setPullRequests([]);
+      setLabelStats(null);
       setReviewedPrs([]);
     } finally {
-      setLoading(false);
+      unlisten();
+      if (requestIdRef.current === requestId) {
+        requestIdRef.current = null;
+        setLoading(false);
+      }
     }
   }, []);
 
+  const cancelFetch = useCallback(async () => {
+    const requestId = requestIdRef.current;
+    if (!requestId) {
+      return;
+    }
+
+    requestIdRef.current = null;
+    setLoading(false);
+    await tauriService.cancelFetch(requestId);
+  }, []);
+
   const fetchOrganizations = useCallback(async (
     startDate: string,
     endDate: string
@@ -93,17 +147,25 @@ export function useGitHubData(): UseGitHubDataReturn {
     setError(null);
   }, []);
 
+  const replacePullRequests = useCallback((prs: PullRequest[]) => {
+    setPullRequests(prs);
+    setLabelStats(null);
+  }, []);
+
   return {
     pullRequests,
     reviewedPrs,
+    labelStats,
     organizations,
     loading,
     orgsLoading,
     error,
     hasSearched,
+    progress,
     fetchReport,
     fetchOrganizations,
+    cancelFetch,
     clearError,
-    setPullRequests,
+    setPullRequests: replacePullRequests,
   };
 }
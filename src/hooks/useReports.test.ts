@@ -181,7 +181,7 @@ describe("useReports", () => {
         return { success: true, reports: [], error: null };
       }
       if (cmd === "save_report") {
-        return { success: false, error: "Save failed" };
+        return { success: false, error: { code: "io", message: "Save failed" } };
       }
     });
     const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
@@ -210,7 +210,7 @@ describe("useReports", () => {
         return { success: true, reports: [mockSavedReport], error: null };
       }
       if (cmd === "delete_report") {
-        return { success: false, error: "Delete failed" };
+        return { success: false, error: { code: "io", message: "Delete failed" } };
       }
     });
     const consoleSpy = vi.spyOn(console, "error").mockImplementation(() => {});
@@ -69,7 +69,7 @@ export function useReports(): UseReportsReturn {
       if (result.success) {
         setSavedReports((prev) => [report, ...prev]);
       } else {
-        throw new Error(result.error || "Failed to save report");
+        throw new Error(result.error?.message || "Failed to save report");
       }
     } catch (err) {
       console.error("Failed to save report:", err);
@@ -95,7 +95,7 @@ export function useReports(): UseReportsReturn {
           prev.map((r) => (r.id === reportId ? updatedReport : r))
         );
       } else {
-        throw new Error(result.error || "Failed to update report");
+        throw new Error(result.error?.message || "Failed to update report");
       }
     } catch (err) {
       console.error("Failed to update report:", err);
@@ -109,7 +109,7 @@ export function useReports(): UseReportsReturn {
       if (result.success) {
         setSavedReports((prev) => prev.filter((r) => r.id !== reportId));
       } else {
-        throw new Error(result.error || "Failed to delete report");
+        throw new Error(result.error?.message || "Failed to delete report");
       }
     } catch (err) {
       console.error("Failed to delete report:", err);
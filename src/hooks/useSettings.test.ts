@@ -31,6 +31,7 @@ describe("useSettings", () => {
     });
 
     expect(result.current.apiKey).toBe("sk-test-key");
+    expect(result.current.apiKeyRequired).toBe(true);
     expect(invoke).toHaveBeenCalledWith("load_settings");
   });
 
@@ -101,6 +102,23 @@ describe("useSettings", () => {
     consoleSpy.mockRestore();
   });
 
+  it("does not require an API key for local providers", async () => {
+    vi.mocked(invoke).mockImplementation(async () => ({
+      success: true,
+      settings: { api_key: null, ai_provider: "ollama" },
+      error: null,
+    }));
+
+    const { result } = renderHook(() => useSettings());
+
+    await waitFor(() => {
+      expect(result.current.isLoading).toBe(false);
+    });
+
+    expect(result.current.aiProvider).toBe("ollama");
+    expect(result.current.apiKeyRequired).toBe(false);
+  });
+
   it("handles empty settings response", async () => {
     vi.mocked(invoke).mockImplementation(async () => ({
       success: true,
@@ -1,15 +1,23 @@
 import { useState, useEffect, useCallback } from "react";
 import * as tauriService from "@/services/tauri";
+import type { AiProvider } from "@/types";
+
+/** Providers that run without an API key, typically on this machine. */
+const KEYLESS_PROVIDERS: AiProvider[] = ["ollama", "openai_compatible"];
 
 interface UseSettingsReturn {
   apiKey: string;
   setApiKey: (key: string) => void;
+  aiProvider: AiProvider;
+  /** Whether AI summaries need `apiKey` with the configured provider. */
+  apiKeyRequired: boolean;
   saveSettings: () => Promise<void>;
   isLoading: boolean;
 }
 
 export function useSettings(): UseSettingsReturn {
   const [apiKey, setApiKey] = useState("");
+  const [aiProvider, setAiProvider] = useState<AiProvider>("openai");
   const [isLoading, setIsLoading] = useState(true);
 
   useEffect(() => {
@@ -19,6 +27,9 @@ export function useSettings(): UseSettingsReturn {
         if (result.success && result.settings?.api_key) {
           setApiKey(result.settings.api_key);
         }
+        if (result.success && result.settings?.ai_provider) {
+          setAiProvider(result.settings.ai_provider);
+        }
       } catch (err) {
         console.error("Failed to load settings:", err);
       } finally {
@@ -41,6 +52,8 @@ export function useSettings(): UseSettingsReturn {
   return {
     apiKey,
     setApiKey,
+    aiProvider,
+    apiKeyRequired: !KEYLESS_PROVIDERS.includes(aiProvider),
     saveSettings,
     isLoading,
   };
@@ -1,62 +1,157 @@
 import { invoke } from "@tauri-apps/api/core";
+import { listen, type UnlistenFn } from "@tauri-apps/api/event";
 import type {
   FetchResult,
   AiResult,
   OrganizationsResult,
   SaveResult,
+  AppSettings,
   LoadSettingsResult,
   LoadReportsResult,
   SavedReport,
   PullRequest,
   ReviewedResult,
+  ReviewedPullRequest,
+  LocalGrouping,
+  Issue,
+  IssuesResult,
+  EnvironmentReport,
+  FetchProgress,
+  AiProgress,
+  AiChunk,
+  AiProvider,
+  ModelsResult,
+  ChunkStrategy,
+  PromptTemplate,
+  PromptTemplatesResult,
+  Locale,
+  SummaryFormat,
 } from "@/types";
 
 export async function fetchGitHubActivity(
   startDate: string,
   endDate: string,
-  orgName: string | null
+  orgName: string | null,
+  requestId: string | null = null
 ): Promise<FetchResult> {
   return invoke<FetchResult>("fetch_github_activity", {
     startDate,
     endDate,
     orgName,
+    requestId,
   });
 }
 
 export async function fetchOrganizations(
   startDate: string,
-  endDate: string
+  endDate: string,
+  requestId: string | null = null
 ): Promise<OrganizationsResult> {
   return invoke<OrganizationsResult>("fetch_organizations", {
     startDate,
     endDate,
+    requestId,
   });
 }
 
 export async function fetchReviewedPrs(
   startDate: string,
-  endDate: string
+  endDate: string,
+  requestId: string | null = null
 ): Promise<ReviewedResult> {
   return invoke<ReviewedResult>("fetch_reviewed_prs", {
     startDate,
     endDate,
+    requestId,
+  });
+}
+
+export async function fetchIssues(
+  startDate: string,
+  endDate: string,
+  orgName: string | null,
+  requestId: string | null = null
+): Promise<IssuesResult> {
+  return invoke<IssuesResult>("fetch_issues", {
+    startDate,
+    endDate,
+    orgName,
+    requestId,
+  });
+}
+
+export async function fetchLocalActivity(
+  startDate: string,
+  endDate: string,
+  groupBy: LocalGrouping | null = null,
+  requestId: string | null = null
+): Promise<FetchResult> {
+  return invoke<FetchResult>("fetch_local_activity", {
+    startDate,
+    endDate,
+    groupBy,
+    requestId,
   });
 }
 
+export async function cancelFetch(requestId: string): Promise<boolean> {
+  return invoke<boolean>("cancel_fetch", { requestId });
+}
+
 export async function generateAiSummary(
   apiKey: string,
   prs: PullRequest[],
   dateRange: string,
-  orgName: string
+  orgName: string,
+  issues: Issue[] | null = null,
+  reviewedPrs: ReviewedPullRequest[] | null = null,
+  chunking: ChunkStrategy | null = null,
+  template: string | null = null,
+  rubricPath: string | null = null,
+  level: string | null = null,
+  language: Locale | null = null,
+  format: SummaryFormat | null = null,
+  requestId: string | null = null
 ): Promise<AiResult> {
   return invoke<AiResult>("generate_ai_summary", {
     apiKey,
     prs,
     dateRange,
     orgName,
+    issues,
+    reviewedPrs,
+    chunking,
+    template,
+    rubricPath,
+    level,
+    language,
+    format,
+    requestId,
   });
 }
 
+export async function listPromptTemplates(): Promise<PromptTemplatesResult> {
+  return invoke<PromptTemplatesResult>("list_prompt_templates");
+}
+
+export async function savePromptTemplate(
+  template: PromptTemplate
+): Promise<SaveResult> {
+  return invoke<SaveResult>("save_prompt_template", { template });
+}
+
+export async function deletePromptTemplate(name: string): Promise<SaveResult> {
+  return invoke<SaveResult>("delete_prompt_template", { name });
+}
+
+export async function listAiModels(
+  apiKey: string | null = null,
+  provider: AiProvider | null = null,
+  baseUrl: string | null = null
+): Promise<ModelsResult> {
+  return invoke<ModelsResult>("list_ai_models", { apiKey, provider, baseUrl });
+}
+
 export async function loadSettings(): Promise<LoadSettingsResult> {
   return invoke<LoadSettingsResult>("load_settings");
 }
@@ -65,6 +160,12 @@ export async function saveSettings(apiKey: string): Promise<SaveResult> {
   return invoke<SaveResult>("save_settings", { apiKey });
 }
 
+export async function updateSettings(
+  settings: AppSettings
+): Promise<SaveResult> {
+  return invoke<SaveResult>("update_settings", { settings });
+}
+
 export async function loadReports(): Promise<LoadReportsResult> {
   return invoke<LoadReportsResult>("load_reports");
 }
@@ -77,3 +178,30 @@ export async function deleteReport(reportId: string): Promise<SaveResult> {
   return invoke<SaveResult>("delete_report", { reportId });
 }
 
+export async function clearActivityCache(): Promise<SaveResult> {
+  return invoke<SaveResult>("clear_activity_cache");
+}
+
+export async function checkEnvironment(): Promise<EnvironmentReport> {
+  return invoke<EnvironmentReport>("check_environment");
+}
+
+export async function onFetchProgress(
+  handler: (progress: FetchProgress) => void
+): Promise<UnlistenFn> {
+  return listen<FetchProgress>("fetch-progress", (event) =>
+    handler(event.payload)
+  );
+}
+
+export async function onAiProgress(
+  handler: (progress: AiProgress) => void
+): Promise<UnlistenFn> {
+  return listen<AiProgress>("ai-progress", (event) => handler(event.payload));
+}
+
+export async function onAiChunk(
+  handler: (chunk: AiChunk) => void
+): Promise<UnlistenFn> {
+  return listen<AiChunk>("ai-chunk", (event) => handler(event.payload));
+}
@@ -53,6 +53,11 @@ export const createMockFetchResult = (
 ): FetchResult => ({
   success: true,
   data: prs,
+  complete: true,
+  stale: false,
+  asOf: null,
+  sizeStats: null,
+  labelStats: null,
   error: null,
 });
 
@@ -61,6 +66,9 @@ export const createMockReviewedResult = (
 ): ReviewedResult => ({
   success: true,
   data: prs,
+  complete: true,
+  stale: false,
+  asOf: null,
   error: null,
 });
 
@@ -69,6 +77,9 @@ export const createMockOrgsResult = (
 ): OrganizationsResult => ({
   success: true,
   organizations: orgs,
+  complete: true,
+  stale: false,
+  asOf: null,
   error: null,
 });
 
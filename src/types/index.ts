@@ -5,6 +5,7 @@
 export interface Repository {
   name: string;
   nameWithOwner: string;
+  host?: string;
 }
 
 export interface PullRequest {
@@ -15,6 +16,46 @@ export interface PullRequest {
   createdAt?: string;
   number?: number;
   repository: Repository;
+  size?: PullRequestSize;
+  labels?: string[];
+  milestone?: string;
+  closingIssues?: IssueReference[];
+}
+
+export interface IssueReference {
+  number: number;
+  title: string;
+  url: string;
+}
+
+export interface PullRequestSize {
+  additions: number;
+  deletions: number;
+  changedFiles: number;
+  commits: number;
+}
+
+export interface SizeStats {
+  measuredPrs: number;
+  additions: number;
+  deletions: number;
+  changedFiles: number;
+  commits: number;
+  medianLinesChanged: number;
+  small: number;
+  medium: number;
+  large: number;
+}
+
+export interface LabelCount {
+  name: string;
+  count: number;
+  prUrls: string[];
+}
+
+export interface LabelStats {
+  labels: LabelCount[];
+  milestones: LabelCount[];
 }
 
 export interface Author {
@@ -28,12 +69,51 @@ export interface ReviewedPullRequest {
   createdAt: string;
   author: Author;
   repository: Repository;
+  review?: ReviewDetails;
+}
+
+export type ReviewState =
+  | "approved"
+  | "changes_requested"
+  | "commented"
+  | "dismissed";
+
+export interface ReviewDetails {
+  commentCount: number;
+  state: ReviewState | null;
+  requestedAt: string | null;
+  firstReviewedAt: string | null;
+  minutesToFirstReview: number | null;
+}
+
+export type IssueInvolvement = "opened" | "closed" | "commented";
+
+export interface Issue {
+  title: string;
+  url: string;
+  body: string | null;
+  state: string;
+  createdAt: string;
+  closedAt: string | null;
+  number?: number;
+  repository: Repository;
+  involvement: IssueInvolvement;
+}
+
+export interface IssuesResult {
+  success: boolean;
+  data: Issue[] | null;
+  complete: boolean;
+  error: AppError | null;
 }
 
 export interface ReviewedResult {
   success: boolean;
   data: ReviewedPullRequest[] | null;
-  error: string | null;
+  complete: boolean;
+  stale: boolean;
+  asOf: string | null;
+  error: AppError | null;
 }
 
 // ============================================
@@ -43,38 +123,133 @@ export interface ReviewedResult {
 export interface FetchResult {
   success: boolean;
   data: PullRequest[] | null;
-  error: string | null;
+  complete: boolean;
+  stale: boolean;
+  asOf: string | null;
+  sizeStats: SizeStats | null;
+  labelStats: LabelStats | null;
+  error: AppError | null;
 }
 
 export interface AiResult {
   success: boolean;
   summary: string | null;
-  error: string | null;
+  stopped?: boolean;
+  /** PRs matched to each rubric competency, when a rubric is configured. */
+  rubric?: CompetencyEvidence[] | null;
+  /** The summary's sections, when requested as JSON; `summary` holds them as Markdown. */
+  structured?: StructuredSummary | null;
+  error: AppError | null;
+}
+
+export type SummaryFormat = "markdown" | "json";
+
+export interface CitedPoint {
+  text: string;
+  prUrls: string[];
+}
+
+export interface StructuredSummary {
+  impactSummary: CitedPoint;
+  achievements: CitedPoint[];
+  growth: CitedPoint[];
+  collaboration: CitedPoint[];
+  talkingPoints: CitedPoint[];
+}
+
+export interface Evidence {
+  title: string;
+  url: string;
+  nameWithOwner: string;
+  note: string;
+}
+
+export interface CompetencyEvidence {
+  competencyId: string;
+  competency: string;
+  evidence: Evidence[];
 }
 
 export interface OrganizationsResult {
   success: boolean;
   organizations: string[] | null;
-  error: string | null;
+  complete: boolean;
+  stale: boolean;
+  asOf: string | null;
+  error: AppError | null;
+}
+
+export interface PromptTemplate {
+  name: string;
+  content: string;
+  /** `true` for the built-in default, which has no file of its own. */
+  builtin: boolean;
+}
+
+export interface PromptTemplatesResult {
+  success: boolean;
+  templates: PromptTemplate[] | null;
+  error: AppError | null;
+}
+
+export interface ModelsResult {
+  success: boolean;
+  models: string[] | null;
+  error: AppError | null;
 }
 
 export interface SaveResult {
   success: boolean;
-  error: string | null;
+  error: AppError | null;
 }
 
 // ============================================
 // Settings & Reports Types
 // ============================================
 
+export interface GitHubHost {
+  host: string;
+  token?: string | null;
+}
+
+export interface GitLabInstance {
+  url: string;
+  token: string;
+}
+
+export type DataSource = "gh_cli" | "github_api" | "fixture";
+
+export type AiProvider =
+  | "openai"
+  | "anthropic"
+  | "azure_openai"
+  | "openai_compatible"
+  | "ollama";
+
+export type LocalGrouping = "merge" | "day";
+
 export interface AppSettings {
   api_key: string | null;
+  github_token?: string | null;
+  github_hosts?: GitHubHost[];
+  gitlab_instances?: GitLabInstance[];
+  local_repositories?: string[];
+  author_emails?: string[];
+  data_source?: DataSource;
+  fixture_dir?: string | null;
+  gh_path?: string | null;
+  ai_provider?: AiProvider;
+  ai_model?: string | null;
+  ai_base_url?: string | null;
+  azure_api_version?: string | null;
+  rubric_path?: string | null;
+  rubric_level?: string | null;
 }
 
 export interface LoadSettingsResult {
   success: boolean;
   settings: AppSettings | null;
-  error: string | null;
+  error: AppError | null;
 }
 
 export interface SavedReport {
@@ -91,7 +266,7 @@ export interface SavedReport {
 export interface LoadReportsResult {
   success: boolean;
   reports: SavedReport[] | null;
-  error: string | null;
+  error: AppError | null;
 }
 
 // ============================================
@@ -118,3 +293,83 @@ export interface DateRange {
   end: string;
 }
 
+export type ErrorCode =
+  | "gh_not_installed"
+  | "not_authenticated"
+  | "rate_limited"
+  | "network"
+  | "api"
+  | "parse"
+  | "io"
+  | "cache"
+  | "config"
+  | "invalid_input"
+  | "cancelled";
+
+export interface AppError {
+  code: ErrorCode;
+  message: string;
+  host?: string;
+  status?: number;
+  retryAfter?: number;
+}
+
+export interface GhAccount {
+  host: string;
+  login: string;
+  active: boolean;
+  authenticated: boolean;
+  scopes: string[];
+}
+
+export interface RateLimit {
+  host: string;
+  resource: string;
+  limit: number;
+  remaining: number;
+  resetAt: string | null;
+}
+
+export interface EnvironmentReport {
+  ghPath: string | null;
+  ghVersion: string | null;
+  accounts: GhAccount[];
+  rateLimits: RateLimit[];
+  dataDir: string;
+  dataDirWritable: boolean;
+  aiKeyConfigured: boolean;
+  problems: AppError[];
+}
+
+export type FetchPhase = "searching" | "enriching" | "done";
+
+export interface FetchProgress {
+  requestId: string | null;
+  kind: "authored" | "reviewed";
+  phase: FetchPhase;
+  pagesFetched: number;
+  itemsFetched: number;
+}
+
+/** UI locale, also the language AI summaries are written in. */
+export type Locale = "en-US" | "pt-BR";
+
+export type AiPhase = "summarizing" | "generating" | "mapping" | "done";
+
+export interface AiProgress {
+  requestId: string | null;
+  phase: AiPhase;
+  /** Completion tokens generated so far in the current pass. */
+  tokens: number;
+  chunksDone: number;
+  /** Chunks the PRs were split into, or 0 when they fit in one prompt. */
+  chunks: number;
+}
+
+/** How a PR set too large for one prompt is split before summarizing. */
+export type ChunkStrategy = "auto" | "single" | "repository" | "month";
+
+export interface AiChunk {
+  requestId: string | null;
+  delta: string;
+}